@@ -1,3 +1,6 @@
 fn main() {
-    tauri_build::build()
+    // 仅桌面端需要生成 Tauri 上下文，命令行工具可在没有 Tauri 依赖的情况下构建
+    if std::env::var_os("CARGO_FEATURE_DESKTOP").is_some() {
+        tauri_build::build()
+    }
 }
@@ -0,0 +1,255 @@
+//! 通过视频详情接口逐个检查收藏的视频能否观看，并按不可用的原因分类保存结果
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::Local;
+use tokio::task::JoinSet;
+use crate::bilibili::{BilibiliClient, FavoriteResource, ResourceId, ResourceType, VideoViewResponse};
+use crate::config::{get_config_dir, AppConfig};
+
+/// 每批同时检查的视频数量
+pub const BATCH_SIZE: usize = 8;
+
+/// 两批请求之间的间隔，避免触发风控
+const BATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    Available,
+    /// 被UP主删除
+    DeletedByUploader,
+    /// 因版权等原因被下架
+    Copyright,
+    /// 仅UP主自己可见或审核中
+    Private,
+    /// 所在地区无法观看
+    RegionRestricted,
+    /// 付费、充电专属或付费合集内容
+    PaidOnly,
+    /// 请求失败或无法识别的错误码
+    Unknown,
+}
+
+impl Availability {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Availability::Available => "正常",
+            Availability::DeletedByUploader => "UP主删除",
+            Availability::Copyright => "版权下架",
+            Availability::Private => "不可见",
+            Availability::RegionRestricted => "地区限制",
+            Availability::PaidOnly => "付费内容",
+            Availability::Unknown => "未知",
+        }
+    }
+
+    /// 根据视频详情接口的结果分类
+    ///
+    /// 被删除和被下架的视频都返回 -404 或 62002，只能借助收藏夹中的 attr 区分：UP主删除的视频 attr 为 9。
+    pub fn classify(resource: &FavoriteResource, response: &VideoViewResponse) -> Self {
+        let deleted_by_uploader = resource.attr == 9;
+        match response.code {
+            0 => match &response.data {
+                Some(data) if data.is_paid() => Availability::PaidOnly,
+                _ => Availability::Available,
+            },
+            -404 if deleted_by_uploader => Availability::DeletedByUploader,
+            -404 => Availability::Copyright,
+            62002 | 62004 | 62012 if deleted_by_uploader => Availability::DeletedByUploader,
+            62002 | 62004 | 62012 => Availability::Private,
+            -10403 | 6002003 => Availability::RegionRestricted,
+            _ if response.message.contains("地区") => Availability::RegionRestricted,
+            _ => Availability::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityEntry {
+    pub folder_id: i64,
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub resource_type: ResourceType,
+    pub bvid: String,
+    /// 收藏夹中显示的标题，失效视频为“已失效视频”
+    pub title: String,
+    pub upper: Option<String>,
+    pub status: Availability,
+    /// 视频详情接口返回的错误码，请求失败时为空
+    pub code: Option<i32>,
+    pub message: String,
+    pub checked_at: i64,
+}
+
+impl AvailabilityEntry {
+    pub fn resource_id(&self) -> ResourceId {
+        ResourceId { id: self.id, resource_type: self.resource_type }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AvailabilityReport {
+    pub updated_at: i64,
+    pub entries: Vec<AvailabilityEntry>,
+    /// 各分类的数量
+    pub counts: BTreeMap<Availability, usize>,
+}
+
+impl AvailabilityReport {
+    /// 用新的检查结果替换对应收藏夹的旧结果
+    pub fn merge(&mut self, folder_ids: &[i64], entries: Vec<AvailabilityEntry>) {
+        self.entries.retain(|e| !folder_ids.contains(&e.folder_id));
+        self.entries.extend(entries);
+        self.refresh();
+    }
+
+    /// 移除已从收藏夹中删除的内容
+    pub fn remove(&mut self, folder_id: i64, resources: &[ResourceId]) {
+        self.entries.retain(|e| e.folder_id != folder_id || !resources.contains(&e.resource_id()));
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.entries.sort_by_key(|e| (e.folder_id, e.status, e.id));
+        self.counts.clear();
+        for entry in &self.entries {
+            *self.counts.entry(entry.status).or_default() += 1;
+        }
+        self.updated_at = Local::now().timestamp();
+    }
+}
+
+fn report_path(up_mid: &str) -> PathBuf {
+    get_config_dir().join("availability").join(format!("{}.json", up_mid))
+}
+
+pub fn load_report(up_mid: &str) -> Result<AvailabilityReport, String> {
+    let path = report_path(up_mid);
+    if !path.exists() {
+        return Ok(AvailabilityReport::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("读取检查结果失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析检查结果失败: {}", e))
+}
+
+pub fn save_report(up_mid: &str, report: &AvailabilityReport) -> Result<(), String> {
+    let path = report_path(up_mid);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("序列化检查结果失败: {}", e))?;
+    fs::write(&path, content)
+        .map_err(|e| format!("写入检查结果失败: {}", e))
+}
+
+async fn check_one(client: BilibiliClient, config: AppConfig, folder_id: i64, resource: FavoriteResource) -> AvailabilityEntry {
+    let (status, code, message) = match client.get_video_view(resource.id, &config).await {
+        Ok(response) => (Availability::classify(&resource, &response), Some(response.code), response.message),
+        Err(error) => (Availability::Unknown, None, error),
+    };
+
+    AvailabilityEntry {
+        folder_id,
+        id: resource.id,
+        resource_type: resource.resource_type,
+        bvid: resource.bvid,
+        title: resource.title,
+        upper: resource.upper.map(|u| u.name),
+        status,
+        code,
+        message,
+        checked_at: Local::now().timestamp(),
+    }
+}
+
+/// 分批检查视频能否观看，每批最多同时发出 `BATCH_SIZE` 个请求；音频、合集等非视频内容不检查
+pub async fn check_resources(client: &BilibiliClient, config: &AppConfig, resources: Vec<(i64, FavoriteResource)>) -> Vec<AvailabilityEntry> {
+    let videos: Vec<(i64, FavoriteResource)> = resources.into_iter()
+        .filter(|(_, r)| r.resource_type == ResourceType::Video)
+        .collect();
+
+    let mut entries = Vec::with_capacity(videos.len());
+    for (index, batch) in videos.chunks(BATCH_SIZE).enumerate() {
+        if index > 0 {
+            tokio::time::sleep(BATCH_INTERVAL).await;
+        }
+
+        let mut tasks = JoinSet::new();
+        for (folder_id, resource) in batch {
+            tasks.spawn(check_one(client.clone(), config.clone(), *folder_id, resource.clone()));
+        }
+        while let Some(result) = tasks.join_next().await {
+            if let Ok(entry) = result {
+                entries.push(entry);
+            }
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn resource(attr: i32) -> FavoriteResource {
+        serde_json::from_value(json!({
+            "id": 1, "type": 2, "title": "已失效视频", "duration": 60,
+            "attr": attr, "ctime": 0, "pubtime": 0, "fav_time": 0
+        })).unwrap()
+    }
+
+    fn classify(attr: i32, response: serde_json::Value) -> Availability {
+        Availability::classify(&resource(attr), &serde_json::from_value(response).unwrap())
+    }
+
+    fn error(code: i32, message: &str) -> serde_json::Value {
+        json!({ "code": code, "message": message, "data": null })
+    }
+
+    #[test]
+    fn classifies_successful_responses() {
+        let view = |data: serde_json::Value| json!({ "code": 0, "message": "0", "data": data });
+        assert_eq!(classify(0, view(json!({ "aid": 1 }))), Availability::Available);
+        assert_eq!(classify(0, view(json!({ "aid": 1, "rights": { "pay": 0, "ugc_pay": 0, "arc_pay": 0 } }))), Availability::Available);
+        assert_eq!(classify(0, json!({ "code": 0, "message": "0" })), Availability::Available);
+        assert_eq!(classify(0, view(json!({ "aid": 1, "rights": { "ugc_pay": 1 } }))), Availability::PaidOnly);
+        assert_eq!(classify(0, view(json!({ "aid": 1, "rights": { "arc_pay": 1 } }))), Availability::PaidOnly);
+        assert_eq!(classify(0, view(json!({ "aid": 1, "is_upower_exclusive": true }))), Availability::PaidOnly);
+        assert_eq!(classify(0, view(json!({ "aid": 1, "is_chargeable_season": true }))), Availability::PaidOnly);
+    }
+
+    #[test]
+    fn classifies_error_codes() {
+        let cases = [
+            (9, -404, "啥都木有", Availability::DeletedByUploader),
+            (1, -404, "啥都木有", Availability::Copyright),
+            (0, -404, "啥都木有", Availability::Copyright),
+            (9, 62002, "稿件不可见", Availability::DeletedByUploader),
+            (0, 62002, "稿件不可见", Availability::Private),
+            (0, 62004, "稿件审核中", Availability::Private),
+            (0, 62012, "仅UP主自己可见", Availability::Private),
+            (9, 62012, "仅UP主自己可见", Availability::DeletedByUploader),
+            (0, -10403, "抱歉您所在地区不可观看！", Availability::RegionRestricted),
+            (0, 6002003, "抱歉您所在地区不可观看！", Availability::RegionRestricted),
+            (0, -403, "该地区无法访问", Availability::RegionRestricted),
+            (0, -403, "访问权限不足", Availability::Unknown),
+            (9, -500, "服务器错误", Availability::Unknown),
+            (0, 12345, "", Availability::Unknown),
+        ];
+        for (attr, code, message, expected) in cases {
+            assert_eq!(classify(attr, error(code, message)), expected, "attr={} code={}", attr, code);
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
-use crate::config::AppConfig;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+use reqwest::RequestBuilder;
+use crate::config::{AppConfig, NetworkConfig};
+use crate::headers::{HeaderProfile, Page};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FavoriteFolder {
@@ -24,11 +28,23 @@ pub struct FavoriteFolder {
     pub is_top: bool,
 }
 
+impl FavoriteFolder {
+    /// attr 最低位为 1 表示私密收藏夹
+    pub fn is_private(&self) -> bool {
+        self.attr & 1 == 1
+    }
+
+    /// 距离收藏夹容量上限的剩余空间
+    pub fn available_space(&self) -> i32 {
+        std::cmp::max(MAX_FAVORITES_LIMIT - self.media_count, 0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FavoriteResource {
     pub id: i64,
     #[serde(rename = "type")]
-    pub resource_type: i32,
+    pub resource_type: ResourceType,
     pub title: String,
     #[serde(default)]
     pub cover: String,
@@ -59,6 +75,93 @@ pub struct FavoriteResource {
     pub link: String,
 }
 
+impl FavoriteResource {
+    /// 失效视频的 attr 最低位为 1（如被UP主删除时为 9），标题显示为“已失效视频”
+    pub fn is_invalid(&self) -> bool {
+        self.attr & 1 == 1 || self.title == "已失效视频"
+    }
+}
+
+/// 收藏夹中的资源类型，序列化为接口使用的数字类型码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "i32", into = "i32")]
+pub enum ResourceType {
+    Video,
+    Audio,
+    VideoCollection,
+    Other(i32),
+}
+
+impl ResourceType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceType::Video => "视频",
+            ResourceType::Audio => "音频",
+            ResourceType::VideoCollection => "视频合集",
+            ResourceType::Other(_) => "未知类型",
+        }
+    }
+}
+
+impl From<i32> for ResourceType {
+    fn from(code: i32) -> Self {
+        match code {
+            2 => ResourceType::Video,
+            12 => ResourceType::Audio,
+            21 => ResourceType::VideoCollection,
+            other => ResourceType::Other(other),
+        }
+    }
+}
+
+impl From<ResourceType> for i32 {
+    fn from(resource_type: ResourceType) -> Self {
+        match resource_type {
+            ResourceType::Video => 2,
+            ResourceType::Audio => 12,
+            ResourceType::VideoCollection => 21,
+            ResourceType::Other(code) => code,
+        }
+    }
+}
+
+/// 带类型的资源ID，移动、复制、删除等接口需要同时提交ID和类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResourceId {
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub resource_type: ResourceType,
+}
+
+impl ResourceId {
+    /// 格式：id:type
+    pub fn to_param(self) -> String {
+        format!("{}:{}", self.id, i32::from(self.resource_type))
+    }
+
+    pub fn join_params(resources: &[ResourceId]) -> String {
+        resources.iter().map(|r| r.to_param()).collect::<Vec<_>>().join(",")
+    }
+}
+
+impl From<&FavoriteResource> for ResourceId {
+    fn from(resource: &FavoriteResource) -> Self {
+        Self {
+            id: resource.id,
+            resource_type: resource.resource_type,
+        }
+    }
+}
+
+impl From<&MoveVideoInfo> for ResourceId {
+    fn from(video: &MoveVideoInfo) -> Self {
+        Self {
+            id: video.id,
+            resource_type: video.resource_type,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoUpper {
     pub mid: i64,
@@ -89,8 +192,88 @@ pub struct FavoriteListData {
     #[serde(default)]
     pub count: i32,
     pub list: Vec<FavoriteFolder>,
+    /// 订阅的合集，个别项目格式异常时跳过该项目，不影响收藏夹列表
+    #[serde(default, deserialize_with = "deserialize_lenient_list")]
+    pub season: Option<Vec<CollectedFolder>>,
+}
+
+/// 逐项解析列表，跳过无法解析的项目
+fn deserialize_lenient_list<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let items: Option<Vec<serde_json::Value>> = Option::deserialize(deserializer)?;
+    Ok(items.map(|items| items.into_iter()
+        .filter_map(|item| serde_json::from_value(item).ok())
+        .collect()))
+}
+
+/// 收藏的他人收藏夹或订阅的合集
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectedFolder {
+    pub id: i64,
+    #[serde(default)]
+    pub fid: i64,
+    #[serde(default)]
+    pub mid: i64,
+    #[serde(default)]
+    pub attr: i32,
+    pub title: String,
+    #[serde(default)]
+    pub cover: String,
+    #[serde(default)]
+    pub upper: Option<VideoUpper>,
+    #[serde(default)]
+    pub intro: String,
+    #[serde(default)]
+    pub ctime: i64,
+    #[serde(default)]
+    pub mtime: i64,
     #[serde(default)]
-    pub season: Option<serde_json::Value>,
+    pub state: i32,
+    #[serde(default)]
+    pub fav_state: i32,
+    #[serde(default)]
+    pub media_count: i32,
+    #[serde(default)]
+    pub view_count: i32,
+    /// 11 为收藏夹，21 为合集
+    #[serde(rename = "type")]
+    #[serde(default)]
+    pub collected_type: i32,
+    #[serde(default)]
+    pub link: String,
+}
+
+impl CollectedFolder {
+    pub fn is_season(&self) -> bool {
+        self.collected_type == 21
+    }
+
+    /// 被创建者删除（state 非 0）或设为私密（attr 最低位为 1）
+    pub fn is_unavailable(&self) -> bool {
+        self.state != 0 || self.attr & 1 == 1
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectedListResponse {
+    pub code: i32,
+    pub message: String,
+    #[serde(default)]
+    pub ttl: i32,
+    pub data: Option<CollectedListData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectedListData {
+    #[serde(default)]
+    pub count: i32,
+    #[serde(default, deserialize_with = "deserialize_lenient_list")]
+    pub list: Option<Vec<CollectedFolder>>,
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,28 +319,371 @@ pub struct MoveResponse {
     pub data: Option<i32>,
 }
 
+/// 收藏夹内容按分区统计的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderPartition {
+    pub tid: i64,
+    pub name: String,
+    pub count: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderPartitionResponse {
+    pub code: i32,
+    pub message: String,
+    pub data: Option<Vec<FolderPartition>>,
+}
+
+/// 新建和修改收藏夹接口的响应，data 为修改后的收藏夹信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderEditResponse {
+    pub code: i32,
+    pub message: String,
+    pub data: Option<FavoriteFolder>,
+}
+
+/// `get_favorite_resources` 返回的第一页内容数量
+pub const FIRST_PAGE_SIZE: usize = 20;
+
+/// 单个收藏夹的视频数量上限
+pub const MAX_FAVORITES_LIMIT: i32 = 1000;
+
+/// 收藏夹标题的最大长度（字符数）
+pub const MAX_FOLDER_TITLE_LENGTH: usize = 20;
+
+/// 稍后再看中的视频
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchLaterItem {
+    pub aid: i64,
+    #[serde(default)]
+    pub bvid: String,
+    pub title: String,
+    #[serde(default)]
+    pub pic: String,
+    #[serde(default)]
+    pub duration: i32,
+    #[serde(default)]
+    pub pubdate: i64,
+    #[serde(default)]
+    pub owner: Option<VideoUpper>,
+    /// 观看进度（秒），-1 表示已看完
+    #[serde(default)]
+    pub progress: i32,
+    #[serde(default)]
+    pub add_at: i64,
+    #[serde(default)]
+    pub state: i32,
+}
+
+impl WatchLaterItem {
+    pub fn is_watched(&self) -> bool {
+        self.progress == -1
+    }
+
+    /// 稿件状态为负数时视频不可观看，0 和 1（橙色通过）等非负状态均正常
+    pub fn is_invalid(&self) -> bool {
+        self.state < 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchLaterResponse {
+    pub code: i32,
+    pub message: String,
+    #[serde(default)]
+    pub ttl: i32,
+    pub data: Option<WatchLaterData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchLaterData {
+    #[serde(default)]
+    pub count: i32,
+    #[serde(default)]
+    pub list: Option<Vec<WatchLaterItem>>,
+}
+
+/// 导航栏接口返回的当前登录用户信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavData {
+    #[serde(rename = "isLogin")]
+    pub is_login: bool,
+    #[serde(default)]
+    pub mid: i64,
+    #[serde(default)]
+    pub uname: String,
+    #[serde(default)]
+    pub face: String,
+    #[serde(default)]
+    pub level_info: Option<NavLevelInfo>,
+    /// 1 表示大会员有效
+    #[serde(default, rename = "vipStatus")]
+    pub vip_status: i32,
+    /// 0 无，1 月度大会员，2 年度及以上大会员
+    #[serde(default, rename = "vipType")]
+    pub vip_type: i32,
+    #[serde(default)]
+    pub vip_label: Option<NavVipLabel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavLevelInfo {
+    pub current_level: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavVipLabel {
+    #[serde(default)]
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavResponse {
+    pub code: i32,
+    pub message: String,
+    #[serde(default)]
+    pub ttl: i32,
+    pub data: Option<NavData>,
+}
+
+/// 视频详情接口返回的数据，只保留判断能否观看所需的字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoViewData {
+    pub aid: i64,
+    #[serde(default)]
+    pub bvid: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub rights: VideoRights,
+    /// 充电专属视频
+    #[serde(default)]
+    pub is_upower_exclusive: bool,
+    /// 付费合集中的视频
+    #[serde(default)]
+    pub is_chargeable_season: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VideoRights {
+    #[serde(default)]
+    pub pay: i32,
+    #[serde(default)]
+    pub ugc_pay: i32,
+    #[serde(default)]
+    pub arc_pay: i32,
+}
+
+impl VideoViewData {
+    pub fn is_paid(&self) -> bool {
+        self.rights.pay == 1 || self.rights.ugc_pay == 1 || self.rights.arc_pay == 1
+            || self.is_upower_exclusive || self.is_chargeable_season
+    }
+}
+
+/// 视频详情接口的响应，失败时的 code 用于区分视频不可用的原因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoViewResponse {
+    pub code: i32,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub data: Option<VideoViewData>,
+}
+
+/// 收藏夹内容排序时每次请求提交的资源数量
+pub const RESOURCE_SORT_CHUNK_SIZE: usize = 100;
+
+/// 批量移动、删除内容时每次请求提交的资源数量
+pub const RESOURCE_BATCH_SIZE: usize = 100;
+
+/// 收藏夹内容排序依据
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceSortKey {
+    Pubtime,
+    FavTime,
+    Duration,
+    Title,
+    Uploader,
+}
+
+impl ResourceSortKey {
+    pub fn sort(&self, resources: &mut [FavoriteResource], descending: bool) {
+        match self {
+            ResourceSortKey::Pubtime => resources.sort_by_key(|r| r.pubtime),
+            ResourceSortKey::FavTime => resources.sort_by_key(|r| r.fav_time),
+            ResourceSortKey::Duration => resources.sort_by_key(|r| r.duration),
+            ResourceSortKey::Title => resources.sort_by(|a, b| a.title.cmp(&b.title)),
+            ResourceSortKey::Uploader => resources.sort_by(|a, b| {
+                let a_name = a.upper.as_ref().map(|u| u.name.as_str()).unwrap_or("");
+                let b_name = b.upper.as_ref().map(|u| u.name.as_str()).unwrap_or("");
+                a_name.cmp(b_name)
+            }),
+        }
+        if descending {
+            resources.reverse();
+        }
+    }
+}
+
+/// 移动视频时的选择方向（按收藏时间）
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveDirection {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+impl MoveDirection {
+    /// 按收藏时间排序，先移动的排在前面
+    pub fn sort(&self, resources: &mut [FavoriteResource]) {
+        match self {
+            MoveDirection::NewestFirst => resources.sort_by_key(|r| std::cmp::Reverse(r.fav_time)),
+            MoveDirection::OldestFirst => resources.sort_by_key(|r| r.fav_time),
+        }
+    }
+}
+
+/// 移动视频时的筛选条件，未设置的条件不参与筛选
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MoveFilter {
+    pub fav_time_start: Option<i64>,
+    pub fav_time_end: Option<i64>,
+    pub pubtime_start: Option<i64>,
+    pub pubtime_end: Option<i64>,
+    /// UP主名称或UID
+    pub uploader: Option<String>,
+    pub keyword: Option<String>,
+    pub min_duration: Option<i32>,
+    pub max_duration: Option<i32>,
+    pub ids: Option<Vec<i64>>,
+}
+
+impl MoveFilter {
+    pub fn matches(&self, resource: &FavoriteResource) -> bool {
+        if let Some(ids) = &self.ids {
+            if !ids.contains(&resource.id) {
+                return false;
+            }
+        }
+        if self.fav_time_start.is_some_and(|start| resource.fav_time < start)
+            || self.fav_time_end.is_some_and(|end| resource.fav_time > end)
+            || self.pubtime_start.is_some_and(|start| resource.pubtime < start)
+            || self.pubtime_end.is_some_and(|end| resource.pubtime > end)
+            || self.min_duration.is_some_and(|min| resource.duration < min)
+            || self.max_duration.is_some_and(|max| resource.duration > max)
+        {
+            return false;
+        }
+        if let Some(uploader) = &self.uploader {
+            let matched = resource.upper.as_ref().is_some_and(|u| {
+                u.name.contains(uploader.as_str()) || u.mid.to_string() == *uploader
+            });
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(keyword) = &self.keyword {
+            if !resource.title.to_lowercase().contains(&keyword.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
 pub struct BilibiliClient {
     client: reqwest::Client,
+    headers: HeaderProfile,
+    api_base: String,
 }
 
 impl BilibiliClient {
-    pub fn new() -> Self {
-        Self {
-            client: reqwest::Client::builder()
-                .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36")
-                .build()
-                .unwrap(),
+    /// 使用默认网络设置和请求头创建客户端
+    pub fn new() -> Result<Self, String> {
+        Self::build(&NetworkConfig::default(), HeaderProfile::default())
+    }
+
+    /// 使用配置中的网络设置和请求头创建客户端，离线模式下返回错误
+    pub fn from_config(config: &AppConfig) -> Result<Self, String> {
+        if config.offline {
+            return Err("当前处于离线模式，无法访问网络".to_string());
+        }
+        Self::build(&config.network, config.headers.clone())
+    }
+
+    fn build(network: &NetworkConfig, headers: HeaderProfile) -> Result<Self, String> {
+        if headers.user_agent.trim().is_empty() {
+            return Err("User-Agent不能为空".to_string());
+        }
+
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(proxy) = network.proxy.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+            let scheme = proxy.split_once("://").map(|(scheme, _)| scheme.to_ascii_lowercase());
+            if !matches!(scheme.as_deref(), Some("http" | "https" | "socks5" | "socks5h")) {
+                return Err(format!("不支持的代理地址: {}，请使用 http://、https:// 或 socks5://", proxy));
+            }
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| format!("代理地址无效: {}", e))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(secs) = network.connect_timeout.filter(|s| *s > 0) {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = network.read_timeout.filter(|s| *s > 0) {
+            builder = builder.read_timeout(Duration::from_secs(secs));
+        }
+
+        if let Some(path) = network.ca_bundle.as_deref().filter(|p| !p.trim().is_empty()) {
+            let pem = std::fs::read(path)
+                .map_err(|e| format!("读取CA证书失败: {}", e))?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|e| format!("解析CA证书失败: {}", e))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        // 绑定到 IPv4 的任意地址后只会连接 IPv4 地址
+        if network.ipv4_only {
+            builder = builder.local_address(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         }
+
+        let client = builder.build()
+            .map_err(|e| format!("创建HTTP客户端失败: {}", e))?;
+
+        Ok(Self {
+            client,
+            headers,
+            api_base: "https://api.bilibili.com".to_string(),
+        })
+    }
+
+    fn get(&self, url: &str, page: Page, config: &AppConfig) -> RequestBuilder {
+        self.headers.apply(self.client.get(url), &page).header("Cookie", &config.cookie)
+    }
+
+    fn post(&self, url: &str, page: Page, config: &AppConfig) -> RequestBuilder {
+        self.headers.apply(self.client.post(url), &page).header("Cookie", &config.cookie)
+    }
+
+    /// 使用自定义接口地址，便于在测试中指向本地模拟服务
+    #[cfg(test)]
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into();
+        self
     }
 
     pub async fn get_favorite_folders(&self, config: &AppConfig) -> Result<Vec<FavoriteFolder>, String> {
-        let url = format!("https://api.bilibili.com/x/v3/fav/folder/created/list-all?up_mid={}", config.up_mid);
+        let url = format!("{}/x/v3/fav/folder/created/list-all?up_mid={}", self.api_base, config.up_mid);
         
-        let response = self.client
-            .get(&url)
-            .header("Referer", "https://www.bilibili.com/")
-            .header("Origin", "https://www.bilibili.com")
-            .header("Cookie", &config.cookie)
+        let response = self.get(&url, Page::Home, config)
             .send()
             .await
             .map_err(|e| format!("请求失败: {}", e))?;
@@ -176,13 +702,9 @@ impl BilibiliClient {
     }
 
     pub async fn get_favorite_resources(&self, favorite_id: i64, config: &AppConfig) -> Result<FavoriteDetailData, String> {
-        let url = format!("https://api.bilibili.com/x/v3/fav/resource/list?media_id={}&pn=1&ps=20&keyword=&order=mtime&type=0&tid=0&platform=web&web_location=333.1387", favorite_id);
+        let url = format!("{}/x/v3/fav/resource/list?media_id={}&pn=1&ps={}&keyword=&order=mtime&type=0&tid=0&platform=web&web_location=333.1387", self.api_base, favorite_id, FIRST_PAGE_SIZE);
         
-        let response = self.client
-            .get(&url)
-            .header("Referer", "https://www.bilibili.com/")
-            .header("Origin", "https://www.bilibili.com")
-            .header("Cookie", &config.cookie)
+        let response = self.get(&url, Page::Home, config)
             .send()
             .await
             .map_err(|e| format!("请求失败: {}", e))?;
@@ -206,13 +728,9 @@ impl BilibiliClient {
         
         if total_pages == 1 {
             let actual_page_size = count.min(20);
-            let url = format!("https://api.bilibili.com/x/v3/fav/resource/list?media_id={}&pn=1&ps={}&keyword=&order=mtime&type=0&tid=0&platform=web&web_location=333.1387", favorite_id, actual_page_size);
+            let url = format!("{}/x/v3/fav/resource/list?media_id={}&pn=1&ps={}&keyword=&order=mtime&type=0&tid=0&platform=web&web_location=333.1387", self.api_base, favorite_id, actual_page_size);
             
-            let response = self.client
-                .get(&url)
-                .header("Referer", "https://www.bilibili.com/")
-                .header("Origin", "https://www.bilibili.com")
-                .header("Cookie", &config.cookie)
+            let response = self.get(&url, Page::Home, config)
                 .send()
                 .await
                 .map_err(|e| format!("请求失败: {}", e))?;
@@ -233,13 +751,9 @@ impl BilibiliClient {
             let mut all_videos = Vec::new();
             
             for page in 1..=total_pages {
-                let url = format!("https://api.bilibili.com/x/v3/fav/resource/list?media_id={}&pn={}&ps={}&keyword=&order=mtime&type=0&tid=0&platform=web&web_location=333.1387", favorite_id, page, page_size);
+                let url = format!("{}/x/v3/fav/resource/list?media_id={}&pn={}&ps={}&keyword=&order=mtime&type=0&tid=0&platform=web&web_location=333.1387", self.api_base, favorite_id, page, page_size);
                 
-                let response = self.client
-                    .get(&url)
-                    .header("Referer", "https://www.bilibili.com/")
-                    .header("Origin", "https://www.bilibili.com")
-                    .header("Cookie", &config.cookie)
+                let response = self.get(&url, Page::Home, config)
                     .send()
                     .await
                     .map_err(|e| format!("请求失败: {}", e))?;
@@ -267,20 +781,149 @@ impl BilibiliClient {
         }
     }
 
-    pub async fn clean_favorite_folder(&self, media_id: i64, config: &AppConfig) -> Result<i32, String> {
-        let url = "https://api.bilibili.com/x/v3/fav/resource/clean";
-        
-        let mut params = std::collections::HashMap::new();
-        params.insert("media_id", media_id.to_string());
-        params.insert("platform", "web".to_string());
-        params.insert("csrf", config.csrf_token.clone());
-        
-        let response = self.client
-            .post(url)
-            .header("Referer", "https://www.bilibili.com/")
-            .header("Origin", "https://www.bilibili.com")
-            .header("Cookie", &config.cookie)
-            .form(&params)
+    /// 使用B站的收藏夹内搜索，逐页获取全部结果
+    ///
+    /// 接口的 type=1 可搜索全部收藏夹，但结果不包含所属收藏夹，因此这里固定使用 type=0 搜索单个收藏夹。
+    pub async fn search_favorite_resources(&self, media_id: i64, keyword: &str, config: &AppConfig) -> Result<Vec<FavoriteResource>, String> {
+        self.get_filtered_resources(media_id, keyword, 0, config).await
+    }
+
+    /// 获取收藏夹中属于某个分区的全部内容
+    pub async fn get_partition_resources(&self, media_id: i64, tid: i64, config: &AppConfig) -> Result<Vec<FavoriteResource>, String> {
+        self.get_filtered_resources(media_id, "", tid, config).await
+    }
+
+    /// 按关键词和分区筛选收藏夹内容并逐页获取，`tid` 为 0 表示不限分区
+    async fn get_filtered_resources(&self, media_id: i64, keyword: &str, tid: i64, config: &AppConfig) -> Result<Vec<FavoriteResource>, String> {
+        let mut resources = Vec::new();
+
+        for page in 1.. {
+            let url = format!("{}/x/v3/fav/resource/list?media_id={}&pn={}&ps={}&order=mtime&type=0&tid={}&platform=web&web_location=333.1387", self.api_base, media_id, page, FIRST_PAGE_SIZE, tid);
+
+            let response = self.get(&url, Page::favlist_folder(&config.up_mid, media_id), config)
+                .query(&[("keyword", keyword)])
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {}", e))?;
+
+            let text = response.text().await
+                .map_err(|e| format!("读取响应失败: {}", e))?;
+
+            let result: FavoriteDetailResponse = serde_json::from_str(&text)
+                .map_err(|e| format!("解析响应失败: {}", e))?;
+
+            if result.code != 0 {
+                return Err(format!("获取第{}页失败: {}", page, result.message));
+            }
+
+            let data = result.data.ok_or_else(|| "没有数据".to_string())?;
+            let medias = data.medias.unwrap_or_default();
+            let done = !data.has_more || medias.is_empty();
+            resources.extend(medias);
+            if done {
+                break;
+            }
+        }
+
+        Ok(resources)
+    }
+
+    /// 收藏夹内容涉及的分区及数量
+    pub async fn get_folder_partitions(&self, media_id: i64, config: &AppConfig) -> Result<Vec<FolderPartition>, String> {
+        let url = format!("{}/x/v3/fav/resource/partition?up_mid={}&media_id={}&web_location=333.1387", self.api_base, config.up_mid, media_id);
+
+        let response = self.get(&url, Page::favlist_folder(&config.up_mid, media_id), config)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: FolderPartitionResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(result.data.unwrap_or_default())
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    /// 新建收藏夹，返回新收藏夹的信息
+    pub async fn create_folder(&self, title: &str, intro: &str, private: bool, config: &AppConfig) -> Result<FavoriteFolder, String> {
+        let url = format!("{}/x/v3/fav/folder/add", self.api_base);
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("title", title.to_string());
+        params.insert("intro", intro.to_string());
+        params.insert("privacy", if private { "1" } else { "0" }.to_string());
+        params.insert("cover", String::new());
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::favlist(&config.up_mid), config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: FolderEditResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        match (result.code, result.data) {
+            (0, Some(folder)) => Ok(folder),
+            _ => Err(format!("API错误: {}", result.message)),
+        }
+    }
+
+    /// 修改收藏夹的标题和公开状态，`private` 为空时保持原公开状态，简介和封面保持不变
+    ///
+    /// 收藏夹列表接口不返回简介和封面，修改前先从收藏夹详情中读取，避免被清空。
+    pub async fn edit_folder(&self, media_id: i64, title: &str, private: Option<bool>, config: &AppConfig) -> Result<FavoriteFolder, String> {
+        let info = self.get_favorite_resources(media_id, config).await?.info;
+        let url = format!("{}/x/v3/fav/folder/edit", self.api_base);
+
+        let private = private.unwrap_or(info.is_private());
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("media_id", media_id.to_string());
+        params.insert("title", title.to_string());
+        params.insert("intro", info.intro);
+        params.insert("privacy", if private { "1" } else { "0" }.to_string());
+        params.insert("cover", info.cover);
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::favlist(&config.up_mid), config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: FolderEditResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        match (result.code, result.data) {
+            (0, Some(folder)) => Ok(folder),
+            _ => Err(format!("API错误: {}", result.message)),
+        }
+    }
+
+    pub async fn clean_favorite_folder(&self, media_id: i64, config: &AppConfig) -> Result<i32, String> {
+        let url = format!("{}/x/v3/fav/resource/clean", self.api_base);
+        
+        let mut params = std::collections::HashMap::new();
+        params.insert("media_id", media_id.to_string());
+        params.insert("platform", "web".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+        
+        let response = self.post(&url, Page::Home, config)
+            .form(&params)
             .send()
             .await
             .map_err(|e| format!("请求失败: {}", e))?;
@@ -299,7 +942,7 @@ impl BilibiliClient {
     }
 
     pub async fn sort_favorite_folders(&self, folder_ids: &[i64], config: &AppConfig) -> Result<(), String> {
-        let url = "https://api.bilibili.com/x/v3/fav/folder/sort";
+        let url = format!("{}/x/v3/fav/folder/sort", self.api_base);
         
         let sort_param = folder_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
         
@@ -307,20 +950,7 @@ impl BilibiliClient {
         params.insert("sort", sort_param);
         params.insert("csrf", config.csrf_token.clone());
         
-        let response = self.client
-            .post(url)
-            .header("Referer", format!("https://space.bilibili.com/{}/favlist", config.up_mid))
-            .header("Origin", "https://space.bilibili.com")
-            .header("Cookie", &config.cookie)
-            .header("Accept", "application/json, text/plain, */*")
-            .header("Accept-Language", "zh-CN,zh-HK;q=0.9,zh;q=0.8,en-US;q=0.7,en;q=0.6")
-            .header("Priority", "u=1, i")
-            .header("Sec-Ch-Ua", "\"Google Chrome\";v=\"141\", \"Not?A_Brand\";v=\"8\", \"Chromium\";v=\"141\"")
-            .header("Sec-Ch-Ua-Mobile", "?0")
-            .header("Sec-Ch-Ua-Platform", "\"Windows\"")
-            .header("Sec-Fetch-Dest", "empty")
-            .header("Sec-Fetch-Mode", "cors")
-            .header("Sec-Fetch-Site", "same-site")
+        let response = self.post(&url, Page::favlist(&config.up_mid), config)
             .form(&params)
             .send()
             .await
@@ -339,25 +969,90 @@ impl BilibiliClient {
         }
     }
 
-    pub async fn move_videos(&self, resource_ids: &[i64], src_media_id: i64, tar_media_id: i64, config: &AppConfig) -> Result<MoveResult, String> {
-        let url = "https://api.bilibili.com/x/v3/fav/resource/move";
-        
-        // 格式：id:type,id:type,... (视频类型为2)
-        let resources = resource_ids.iter().map(|id| format!("{}:2", id)).collect::<Vec<_>>().join(",");
+    /// 删除自己创建的收藏夹，默认收藏夹无法删除
+    pub async fn delete_folders(&self, media_ids: &[i64], config: &AppConfig) -> Result<(), String> {
+        let url = format!("{}/x/v3/fav/folder/del", self.api_base);
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("media_ids", media_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","));
+        params.insert("platform", "web".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::favlist(&config.up_mid), config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: SortResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(())
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    /// 调整收藏夹内视频的顺序
+    ///
+    /// `resources` 为目标顺序下的资源列表。排序接口会把提交的资源按给定顺序
+    /// 置顶，因此大收藏夹按块从后往前提交，最后一块提交完成后即为完整顺序。
+    pub async fn sort_favorite_resources<F>(&self, media_id: i64, resources: &[ResourceId], config: &AppConfig, mut on_progress: F) -> Result<(), String>
+    where
+        F: FnMut(usize, usize),
+    {
+        let url = format!("{}/x/v3/fav/resource/sort", self.api_base);
+        let total = resources.len();
+        let mut processed = 0;
+
+        for chunk in resources.rchunks(RESOURCE_SORT_CHUNK_SIZE) {
+            let sort_param = ResourceId::join_params(chunk);
+
+            let mut params = std::collections::HashMap::new();
+            params.insert("media_id", media_id.to_string());
+            params.insert("sort", sort_param);
+            params.insert("platform", "web".to_string());
+            params.insert("csrf", config.csrf_token.clone());
+
+            let response = self.post(&url, Page::favlist_folder(&config.up_mid, media_id), config)
+                .form(&params)
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {}", e))?;
+
+            let text = response.text().await
+                .map_err(|e| format!("读取响应失败: {}", e))?;
+
+            let result: SortResponse = serde_json::from_str(&text)
+                .map_err(|e| format!("解析响应失败: {}", e))?;
+
+            if result.code != 0 {
+                return Err(format!("API错误: {}", result.message));
+            }
+
+            processed += chunk.len();
+            on_progress(processed, total);
+        }
+
+        Ok(())
+    }
+
+    pub async fn move_videos(&self, resource_ids: &[ResourceId], src_media_id: i64, tar_media_id: i64, config: &AppConfig) -> Result<MoveResult, String> {
+        let url = format!("{}/x/v3/fav/resource/move", self.api_base);
         
         let mut params = std::collections::HashMap::new();
-        params.insert("resources", resources);
+        params.insert("resources", ResourceId::join_params(resource_ids));
         params.insert("src_media_id", src_media_id.to_string());
         params.insert("tar_media_id", tar_media_id.to_string());
         params.insert("mid", config.up_mid.to_string());
         params.insert("platform", "web".to_string());
         params.insert("csrf", config.csrf_token.clone());
         
-        let response = self.client
-            .post(url)
-            .header("Referer", "https://www.bilibili.com/")
-            .header("Origin", "https://www.bilibili.com")
-            .header("Cookie", &config.cookie)
+        let response = self.post(&url, Page::Home, config)
             .form(&params)
             .send()
             .await
@@ -384,6 +1079,335 @@ impl BilibiliClient {
         }
     }
 
+    pub async fn copy_resources(&self, resource_ids: &[ResourceId], src_media_id: i64, tar_media_id: i64, config: &AppConfig) -> Result<i32, String> {
+        let url = format!("{}/x/v3/fav/resource/copy", self.api_base);
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("resources", ResourceId::join_params(resource_ids));
+        params.insert("src_media_id", src_media_id.to_string());
+        params.insert("tar_media_id", tar_media_id.to_string());
+        params.insert("mid", config.up_mid.to_string());
+        params.insert("platform", "web".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::Home, config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: MoveResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(resource_ids.len() as i32)
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    pub async fn delete_resources(&self, resource_ids: &[ResourceId], media_id: i64, config: &AppConfig) -> Result<i32, String> {
+        let url = format!("{}/x/v3/fav/resource/batch-del", self.api_base);
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("resources", ResourceId::join_params(resource_ids));
+        params.insert("media_id", media_id.to_string());
+        params.insert("platform", "web".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::Home, config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: MoveResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(resource_ids.len() as i32)
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    /// 获取收藏的他人收藏夹和订阅的合集（自动翻页）
+    pub async fn get_collected_folders(&self, config: &AppConfig) -> Result<Vec<CollectedFolder>, String> {
+        let page_size = 20;
+        let mut all_folders = Vec::new();
+
+        for page in 1.. {
+            let url = format!("{}/x/v3/fav/folder/collected/list?pn={}&ps={}&up_mid={}&platform=web", self.api_base, page, page_size, config.up_mid);
+
+            let response = self.get(&url, Page::Home, config)
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {}", e))?;
+
+            let text = response.text().await
+                .map_err(|e| format!("读取响应失败: {}", e))?;
+
+            let result: CollectedListResponse = serde_json::from_str(&text)
+                .map_err(|e| format!("解析响应失败: {}", e))?;
+
+            if result.code != 0 {
+                return Err(format!("获取第{}页失败: {}", page, result.message));
+            }
+
+            let Some(data) = result.data else { break };
+            let list = data.list.unwrap_or_default();
+            // 接口偶尔在没有更多内容时仍返回 has_more，空页时停止
+            let done = !data.has_more || list.is_empty();
+            all_folders.extend(list);
+            if done {
+                break;
+            }
+        }
+
+        Ok(all_folders)
+    }
+
+    /// 取消收藏他人的收藏夹或取消订阅合集
+    pub async fn unsubscribe_collected(&self, folder: &CollectedFolder, config: &AppConfig) -> Result<(), String> {
+        let mut params = std::collections::HashMap::new();
+        let url = if folder.is_season() {
+            params.insert("season_id", folder.id.to_string());
+            format!("{}/x/v3/fav/season/unfav", self.api_base)
+        } else {
+            params.insert("media_id", folder.id.to_string());
+            format!("{}/x/v3/fav/folder/unfav", self.api_base)
+        };
+        params.insert("platform", "web".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::Home, config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: SortResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(())
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    /// 获取 Cookie 对应的登录用户信息，未登录时 `is_login` 为 false
+    pub async fn get_nav_info(&self, config: &AppConfig) -> Result<NavData, String> {
+        let url = format!("{}/x/web-interface/nav", self.api_base);
+
+        let response = self.get(&url, Page::Home, config)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: NavResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        // 未登录时返回 -101，但仍带有 isLogin: false 的数据
+        match (result.code, result.data) {
+            (0 | -101, Some(data)) => Ok(data),
+            _ => Err(format!("API错误: {}", result.message)),
+        }
+    }
+
+    /// 获取视频详情，接口返回的错误码原样保留，由调用方判断不可用的原因
+    pub async fn get_video_view(&self, aid: i64, config: &AppConfig) -> Result<VideoViewResponse, String> {
+        let url = format!("{}/x/web-interface/view?aid={}", self.api_base, aid);
+
+        let response = self.get(&url, Page::Home, config)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))
+    }
+
+    pub async fn get_watch_later(&self, config: &AppConfig) -> Result<Vec<WatchLaterItem>, String> {
+        let url = format!("{}/x/v2/history/toview", self.api_base);
+
+        let response = self.get(&url, Page::Home, config)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: WatchLaterResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(result.data.and_then(|d| d.list).unwrap_or_default())
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    async fn post_watch_later(&self, path: &str, params: std::collections::HashMap<&str, String>, config: &AppConfig) -> Result<(), String> {
+        let url = format!("{}{}", self.api_base, path);
+
+        let response = self.post(&url, Page::WatchLater, config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: SortResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(())
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    pub async fn add_to_watch_later(&self, bvid: &str, config: &AppConfig) -> Result<(), String> {
+        let mut params = std::collections::HashMap::new();
+        params.insert("bvid", bvid.to_string());
+        params.insert("csrf", config.csrf_token.clone());
+        self.post_watch_later("/x/v2/history/toview/add", params, config).await
+    }
+
+    pub async fn delete_from_watch_later(&self, aid: i64, config: &AppConfig) -> Result<(), String> {
+        let mut params = std::collections::HashMap::new();
+        params.insert("aid", aid.to_string());
+        params.insert("csrf", config.csrf_token.clone());
+        self.post_watch_later("/x/v2/history/toview/del", params, config).await
+    }
+
+    /// 删除稍后再看中所有已看完的视频
+    pub async fn clear_watched_watch_later(&self, config: &AppConfig) -> Result<(), String> {
+        let mut params = std::collections::HashMap::new();
+        params.insert("viewed", "true".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+        self.post_watch_later("/x/v2/history/toview/del", params, config).await
+    }
+
+    /// 将视频添加到指定收藏夹
+    pub async fn add_to_folders(&self, resource: ResourceId, media_ids: &[i64], config: &AppConfig) -> Result<(), String> {
+        let url = format!("{}/x/v3/fav/resource/deal", self.api_base);
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("rid", resource.id.to_string());
+        params.insert("type", i32::from(resource.resource_type).to_string());
+        params.insert("add_media_ids", media_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","));
+        params.insert("del_media_ids", String::new());
+        params.insert("platform", "web".to_string());
+        params.insert("csrf", config.csrf_token.clone());
+
+        let response = self.post(&url, Page::Home, config)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {}", e))?;
+
+        let text = response.text().await
+            .map_err(|e| format!("读取响应失败: {}", e))?;
+
+        let result: SortResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if result.code == 0 {
+            Ok(())
+        } else {
+            Err(format!("API错误: {}", result.message))
+        }
+    }
+
+    /// 将选中的视频从源收藏夹移动到目标收藏夹，并附带被移动视频的详细信息
+    ///
+    /// 源收藏夹列表只获取一次并按ID建立索引，请求数量与收藏夹大小成线性关系，
+    /// 与选中的视频数量无关。
+    pub async fn move_selected_videos(&self, video_ids: &[i64], src_media_id: i64, tar_media_id: i64, config: &AppConfig) -> Result<MoveResult, String> {
+        // 获取源收藏夹与目标收藏夹信息
+        let src_info = self.get_favorite_resources(src_media_id, config).await?;
+        let tar_info = self.get_favorite_resources(tar_media_id, config).await?;
+
+        // 计算可移动的视频数量
+        let available_space = tar_info.info.available_space();
+
+        if available_space <= 0 {
+            return Ok(MoveResult {
+                src_media_id,
+                tar_media_id,
+                moved_count: 0,
+                success_count: 0,
+                fail_count: 0,
+                message: Some(format!("目标收藏夹已满（{}个视频上限）", MAX_FAVORITES_LIMIT)),
+                videos: Some(vec![]),
+            });
+        }
+
+        let movable_count = std::cmp::min(available_space, src_info.info.media_count);
+        let actual_count = std::cmp::min(movable_count, video_ids.len() as i32);
+
+        if actual_count == 0 {
+            return Ok(MoveResult {
+                src_media_id,
+                tar_media_id,
+                moved_count: 0,
+                success_count: 0,
+                fail_count: 0,
+                message: Some("源收藏夹中没有视频".to_string()),
+                videos: Some(vec![]),
+            });
+        }
+
+        // 一次性获取源收藏夹的全部视频，并按ID建立索引
+        let all_videos = self.get_favorite_videos(src_media_id, src_info.info.media_count, config).await?;
+        let index: std::collections::HashMap<i64, &FavoriteResource> = all_videos.iter().map(|v| (v.id, v)).collect();
+
+        // 不在源收藏夹中的ID无法移动，直接跳过
+        let selected: Vec<&FavoriteResource> = video_ids.iter()
+            .filter_map(|id| index.get(id).copied())
+            .take(actual_count as usize)
+            .collect();
+        let actual_count = selected.len() as i32;
+        let resource_ids: Vec<ResourceId> = selected.iter().map(|video| ResourceId::from(*video)).collect();
+        let video_infos: Vec<MoveVideoInfo> = selected.iter().map(|video| MoveVideoInfo::from(*video)).collect();
+
+        match self.move_videos(&resource_ids, src_media_id, tar_media_id, config).await {
+            Ok(result) => Ok(MoveResult {
+                videos: Some(video_infos),
+                ..result
+            }),
+            Err(error) => Ok(MoveResult {
+                src_media_id,
+                tar_media_id,
+                moved_count: 0,
+                success_count: 0,
+                fail_count: actual_count,
+                message: Some(error),
+                videos: Some(vec![]),
+            }),
+        }
+    }
+
     pub fn is_default_folder(&self, folder: &FavoriteFolder) -> bool {
         folder.title == "默认收藏夹" || folder.title.contains("默认") || folder.attr == 0
     }
@@ -403,8 +1427,307 @@ pub struct MoveResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveVideoInfo {
     pub id: i64,
+    pub resource_type: ResourceType,
     pub title: String,
     pub bvid: String,
     pub upper: Option<String>,
     pub duration: i32,
-}
\ No newline at end of file
+}
+impl From<&FavoriteResource> for MoveVideoInfo {
+    fn from(video: &FavoriteResource) -> Self {
+        Self {
+            id: video.id,
+            resource_type: video.resource_type,
+            title: video.title.clone(),
+            bvid: video.bvid.clone(),
+            upper: video.upper.as_ref().map(|u| u.name.clone()),
+            duration: video.duration,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn resource_json(id: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": 2,
+            "title": format!("视频{}", id),
+            "duration": 60,
+            "bvid": format!("BV{}", id),
+            "attr": 0,
+            "ctime": id,
+            "pubtime": id,
+            "fav_time": id,
+            "upper": { "mid": 1, "name": "UP" }
+        })
+    }
+
+    fn folder_json(id: i64, media_count: i32) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "fid": id,
+            "mid": 1,
+            "title": format!("收藏夹{}", id),
+            "attr": 2,
+            "fav_state": 0,
+            "media_count": media_count,
+            "intro": format!("简介{}", id),
+            "cover": format!("https://i0.hdslb.com/cover{}.jpg", id)
+        })
+    }
+
+    fn query_param(path: &str, key: &str) -> Option<i64> {
+        let query = path.split('?').nth(1)?;
+        query.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, v)| v.parse().ok())
+    }
+
+    /// 模拟收藏夹接口：源收藏夹 1 中有 `src_count` 个视频，目标收藏夹 2 为空。返回接口地址、请求计数和修改收藏夹接口收到的表单。
+    async fn spawn_mock_server(src_count: i32) -> (String, Arc<AtomicUsize>, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let edits = Arc::new(Mutex::new(Vec::new()));
+        let edit_bodies = edits.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let edit_bodies = edit_bodies.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let header_end = loop {
+                        let n = socket.read(&mut chunk).await.unwrap();
+                        buf.extend_from_slice(&chunk[..n]);
+                        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            break pos + 4;
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+                    let content_length = head.lines()
+                        .filter_map(|l| l.split_once(':'))
+                        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+                        .and_then(|(_, v)| v.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    while buf.len() < header_end + content_length {
+                        let n = socket.read(&mut chunk).await.unwrap();
+                        buf.extend_from_slice(&chunk[..n]);
+                    }
+
+                    let path = head.split_whitespace().nth(1).unwrap_or("").to_string();
+                    let body = if path.starts_with("/x/v3/fav/resource/list") {
+                        let media_id = query_param(&path, "media_id").unwrap_or(0);
+                        let pn = query_param(&path, "pn").unwrap_or(1);
+                        let ps = query_param(&path, "ps").unwrap_or(20);
+                        let count = if media_id == 1 { src_count } else { 0 };
+                        let start = (pn - 1) * ps;
+                        let end = std::cmp::min(start + ps, count as i64);
+                        let medias: Vec<_> = (start..end).map(|i| resource_json(i + 1)).collect();
+                        serde_json::json!({
+                            "code": 0,
+                            "message": "0",
+                            "data": { "info": folder_json(media_id, count), "medias": medias, "has_more": end < count as i64 }
+                        })
+                    } else if path.starts_with("/x/v3/fav/folder/collected/list") {
+                        // 第一页之后返回空列表，但 has_more 始终为 true
+                        let list: Vec<_> = match query_param(&path, "pn") {
+                            Some(1) => vec![serde_json::json!({ "id": 10, "title": "合集", "type": 21 })],
+                            _ => Vec::new(),
+                        };
+                        serde_json::json!({ "code": 0, "message": "0", "data": { "count": 1, "list": list, "has_more": true } })
+                    } else if path.starts_with("/x/v3/fav/folder/edit") {
+                        let form = String::from_utf8_lossy(&buf[header_end..header_end + content_length]).to_string();
+                        edit_bodies.lock().unwrap().push(form);
+                        serde_json::json!({ "code": 0, "message": "0", "data": folder_json(1, src_count) })
+                    } else {
+                        serde_json::json!({ "code": 0, "message": "0", "data": 0 })
+                    };
+                    let body = body.to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        (format!("http://{}", addr), requests, edits)
+    }
+
+    async fn count_move_requests(src_count: i32, selected: i64) -> (usize, MoveResult) {
+        let (api_base, requests, _) = spawn_mock_server(src_count).await;
+        let client = BilibiliClient::new().unwrap().with_api_base(api_base);
+        let config = AppConfig::default();
+        let video_ids: Vec<i64> = (1..=selected).collect();
+        let result = client.move_selected_videos(&video_ids, 1, 2, &config).await.unwrap();
+        (requests.load(Ordering::SeqCst), result)
+    }
+
+    #[tokio::test]
+    async fn move_selected_videos_fetches_source_listing_once() {
+        // 源收藏夹信息 + 目标收藏夹信息 + 源收藏夹列表（1000/20 页）+ 移动请求
+        let expected = 2 + 1000 / 20 + 1;
+
+        let (few_requests, few_result) = count_move_requests(1000, 10).await;
+        let (many_requests, many_result) = count_move_requests(1000, 500).await;
+
+        assert_eq!(few_requests, expected);
+        assert_eq!(many_requests, expected);
+        assert_eq!(few_result.moved_count, 10);
+        assert_eq!(many_result.moved_count, 500);
+        assert_eq!(many_result.videos.map(|v| v.len()), Some(500));
+    }
+
+    #[tokio::test]
+    async fn move_selected_videos_requests_grow_linearly_with_folder_size() {
+        let (small, _) = count_move_requests(200, 100).await;
+        let (large, _) = count_move_requests(800, 100).await;
+
+        assert_eq!(small, 2 + 200 / 20 + 1);
+        assert_eq!(large, 2 + 800 / 20 + 1);
+    }
+
+    #[tokio::test]
+    async fn edit_folder_keeps_intro_and_cover() {
+        let (api_base, _, edits) = spawn_mock_server(0).await;
+        let client = BilibiliClient::new().unwrap().with_api_base(api_base);
+        let config = AppConfig::default();
+        client.edit_folder(1, "新名称", Some(true), &config).await.unwrap();
+
+        let edits = edits.lock().unwrap();
+        assert_eq!(edits.len(), 1);
+        let form: std::collections::HashMap<String, String> = url::form_urlencoded::parse(edits[0].as_bytes()).into_owned().collect();
+        assert_eq!(form["title"], "新名称");
+        assert_eq!(form["privacy"], "1");
+        assert_eq!(form["intro"], "简介1");
+        assert_eq!(form["cover"], "https://i0.hdslb.com/cover1.jpg");
+    }
+
+    #[tokio::test]
+    async fn edit_folder_without_privacy_keeps_current_state() {
+        let (api_base, _, edits) = spawn_mock_server(0).await;
+        let client = BilibiliClient::new().unwrap().with_api_base(api_base);
+        let config = AppConfig::default();
+        client.edit_folder(1, "新名称", None, &config).await.unwrap();
+
+        let edits = edits.lock().unwrap();
+        let form: std::collections::HashMap<String, String> = url::form_urlencoded::parse(edits[0].as_bytes()).into_owned().collect();
+        assert_eq!(form["privacy"], "0");
+        assert_eq!(form["intro"], "简介1");
+    }
+
+    #[tokio::test]
+    async fn collected_folders_stop_on_empty_page() {
+        let (api_base, requests, _) = spawn_mock_server(0).await;
+        let client = BilibiliClient::new().unwrap().with_api_base(api_base);
+        let folders = client.get_collected_folders(&AppConfig::default()).await.unwrap();
+        assert_eq!(folders.iter().map(|f| f.id).collect::<Vec<_>>(), vec![10]);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    fn filter_resource(id: i64, fav_time: i64, pubtime: i64, duration: i32, title: &str, upper: (i64, &str)) -> FavoriteResource {
+        let mut json = resource_json(id);
+        json["fav_time"] = fav_time.into();
+        json["pubtime"] = pubtime.into();
+        json["duration"] = duration.into();
+        json["title"] = title.into();
+        json["upper"] = serde_json::json!({ "mid": upper.0, "name": upper.1 });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn move_filter_matches() {
+        let resource = filter_resource(7, 1000, 500, 300, "Rust 入门教程", (42, "编程UP主"));
+        let cases: Vec<(&str, MoveFilter, bool)> = vec![
+            ("空条件", MoveFilter::default(), true),
+            ("ID 命中", MoveFilter { ids: Some(vec![1, 7]), ..Default::default() }, true),
+            ("ID 未命中", MoveFilter { ids: Some(vec![1, 2]), ..Default::default() }, false),
+            ("ID 为空列表", MoveFilter { ids: Some(Vec::new()), ..Default::default() }, false),
+            ("收藏时间起点含边界", MoveFilter { fav_time_start: Some(1000), ..Default::default() }, true),
+            ("收藏时间早于起点", MoveFilter { fav_time_start: Some(1001), ..Default::default() }, false),
+            ("收藏时间终点含边界", MoveFilter { fav_time_end: Some(1000), ..Default::default() }, true),
+            ("收藏时间晚于终点", MoveFilter { fav_time_end: Some(999), ..Default::default() }, false),
+            ("发布时间在范围内", MoveFilter { pubtime_start: Some(400), pubtime_end: Some(600), ..Default::default() }, true),
+            ("发布时间早于起点", MoveFilter { pubtime_start: Some(501), ..Default::default() }, false),
+            ("发布时间晚于终点", MoveFilter { pubtime_end: Some(499), ..Default::default() }, false),
+            ("时长在范围内", MoveFilter { min_duration: Some(300), max_duration: Some(300), ..Default::default() }, true),
+            ("时长过短", MoveFilter { min_duration: Some(301), ..Default::default() }, false),
+            ("时长过长", MoveFilter { max_duration: Some(299), ..Default::default() }, false),
+            ("UP主名称包含", MoveFilter { uploader: Some("编程".to_string()), ..Default::default() }, true),
+            ("UP主UID", MoveFilter { uploader: Some("42".to_string()), ..Default::default() }, true),
+            ("UP主UID不完全相同", MoveFilter { uploader: Some("4".to_string()), ..Default::default() }, false),
+            ("UP主不匹配", MoveFilter { uploader: Some("其他".to_string()), ..Default::default() }, false),
+            ("关键词忽略大小写", MoveFilter { keyword: Some("rust".to_string()), ..Default::default() }, true),
+            ("关键词不匹配", MoveFilter { keyword: Some("Go".to_string()), ..Default::default() }, false),
+            ("组合条件全部满足", MoveFilter {
+                fav_time_start: Some(900), fav_time_end: Some(1100),
+                pubtime_end: Some(500), uploader: Some("42".to_string()),
+                keyword: Some("教程".to_string()), min_duration: Some(60), ids: Some(vec![7]),
+                ..Default::default()
+            }, true),
+            ("组合条件有一项不满足", MoveFilter {
+                fav_time_start: Some(900), uploader: Some("编程".to_string()),
+                keyword: Some("教程".to_string()), max_duration: Some(120),
+                ..Default::default()
+            }, false),
+        ];
+        for (name, filter, expected) in cases {
+            assert_eq!(filter.matches(&resource), expected, "{}", name);
+        }
+
+        let mut without_upper = resource.clone();
+        without_upper.upper = None;
+        assert!(!MoveFilter { uploader: Some("编程".to_string()), ..Default::default() }.matches(&without_upper));
+    }
+
+    #[test]
+    fn move_direction_orders_by_fav_time() {
+        let resources: Vec<FavoriteResource> = [(1, 300), (2, 100), (3, 200)].iter()
+            .map(|&(id, fav_time)| filter_resource(id, fav_time, 0, 60, "视频", (1, "UP")))
+            .collect();
+        let ids = |direction: MoveDirection| {
+            let mut sorted = resources.clone();
+            direction.sort(&mut sorted);
+            sorted.iter().map(|r| r.id).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(MoveDirection::NewestFirst), vec![1, 3, 2]);
+        assert_eq!(ids(MoveDirection::OldestFirst), vec![2, 3, 1]);
+        assert_eq!(ids(MoveDirection::default()), vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn watch_later_invalid_only_for_negative_state() {
+        let item = |state: i32| -> WatchLaterItem {
+            serde_json::from_value(serde_json::json!({ "aid": 1, "title": "视频", "state": state })).unwrap()
+        };
+        assert!(!item(0).is_invalid());
+        assert!(!item(1).is_invalid());
+        assert!(item(-1).is_invalid());
+        assert!(item(-100).is_invalid());
+    }
+
+    #[test]
+    fn folder_list_skips_malformed_seasons() {
+        let data: FavoriteListData = serde_json::from_value(serde_json::json!({
+            "count": 1,
+            "list": [folder_json(1, 0)],
+            "season": [{ "id": 7, "title": "合集" }, { "title": "缺少ID" }, null]
+        })).unwrap();
+        assert_eq!(data.list.len(), 1);
+        assert_eq!(data.season.unwrap().iter().map(|s| s.id).collect::<Vec<_>>(), vec![7]);
+    }
+}
@@ -0,0 +1,801 @@
+//! 无界面命令行工具，与桌面端共用 `~/.bilibili-fav-cleaner/config.json`
+
+use bilibili_favorites_tools_lib as app;
+use app::bilibili::{BilibiliClient, FavoriteFolder, FavoriteResource, MoveDirection, MoveFilter};
+use app::availability::Availability;
+use app::organize::{RenameRule, SplitCriterion};
+use app::browser;
+use app::config::{get_config_path, load_config, save_login_config, AppConfig};
+use app::headers::HeaderProfile;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Table,
+}
+
+/// 拆分收藏夹的依据，时长按 5、20、60 分钟分段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SplitBy {
+    Year,
+    Quarter,
+    Uploader,
+    Partition,
+    Duration,
+}
+
+impl From<SplitBy> for SplitCriterion {
+    fn from(by: SplitBy) -> Self {
+        match by {
+            SplitBy::Year => SplitCriterion::Year,
+            SplitBy::Quarter => SplitCriterion::Quarter,
+            SplitBy::Uploader => SplitCriterion::Uploader,
+            SplitBy::Partition => SplitCriterion::Partition,
+            SplitBy::Duration => SplitCriterion::Duration { bounds: Vec::new() },
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "bilibili-fav-cli", version, about = "哔哩哔哩收藏夹命令行工具")]
+struct Cli {
+    /// 输出格式
+    #[arg(long, short, value_enum, global = true, default_value = "table")]
+    format: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 收藏夹列表
+    Folders {
+        #[command(subcommand)]
+        command: FoldersCommand,
+    },
+    /// 收藏夹详情
+    Folder {
+        #[command(subcommand)]
+        command: FolderCommand,
+    },
+    /// 清理收藏夹中的失效内容，不指定ID时清理全部收藏夹
+    Clean {
+        media_ids: Vec<i64>,
+    },
+    /// 检查视频能否观看并按不可用原因分类，不指定ID时检查全部收藏夹
+    Check {
+        media_ids: Vec<i64>,
+    },
+    /// 移动视频，不指定视频ID时按目标收藏夹剩余容量移动最新收藏的视频
+    Move {
+        source_id: i64,
+        target_id: i64,
+        video_ids: Vec<i64>,
+        /// 未指定视频ID时优先移动最早收藏的视频
+        #[arg(long)]
+        oldest_first: bool,
+    },
+    /// 将多个收藏夹合并到目标收藏夹
+    Merge {
+        target_id: i64,
+        #[arg(required = true)]
+        source_ids: Vec<i64>,
+        /// 删除清空后的源收藏夹
+        #[arg(long)]
+        delete_emptied: bool,
+    },
+    /// 按条件将收藏夹拆分为多个收藏夹
+    Split {
+        media_id: i64,
+        #[arg(long, value_enum)]
+        by: SplitBy,
+        /// 新收藏夹的名称模板，可使用 {title}、{group}、{count}、{index}
+        #[arg(long, default_value = "{title}-{group}")]
+        template: String,
+        /// 数量少于该值的分组留在原收藏夹
+        #[arg(long, default_value_t = 1)]
+        min_count: usize,
+        /// 只显示拆分结果，不实际执行
+        #[arg(long)]
+        preview: bool,
+    },
+    /// 按模板或正则批量重命名收藏夹
+    Rename {
+        #[arg(required = true)]
+        media_ids: Vec<i64>,
+        /// 名称模板，可使用 {title}、{index}、{year}、{count}、{id}
+        #[arg(long, conflicts_with = "pattern", required_unless_present = "pattern")]
+        template: Option<String>,
+        /// 正则表达式，与 --replace 一起使用
+        #[arg(long, requires = "replace")]
+        pattern: Option<String>,
+        /// 替换内容，可用 $1 引用捕获组
+        #[arg(long)]
+        replace: Option<String>,
+        /// 只显示重命名结果，不实际执行
+        #[arg(long)]
+        preview: bool,
+    },
+    /// 按给定顺序排序收藏夹
+    Sort {
+        #[arg(required = true)]
+        folder_ids: Vec<i64>,
+    },
+    /// 搜索收藏内容，默认使用B站的收藏夹内搜索
+    Search {
+        keyword: String,
+        /// 只搜索指定收藏夹
+        #[arg(long)]
+        folder: Option<i64>,
+        /// 在本地索引中搜索，只包含扫描过的收藏夹
+        #[arg(long, conflicts_with = "folder")]
+        local: bool,
+    },
+    /// 导出全部收藏夹及其内容为 JSON
+    Export {
+        /// 输出文件，默认输出到标准输出
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// 收藏夹公开状态
+    Privacy {
+        #[command(subcommand)]
+        command: PrivacyCommand,
+    },
+    /// 收藏夹快照
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommand,
+    },
+    /// 配置管理
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum FoldersCommand {
+    /// 列出全部收藏夹
+    List,
+}
+
+#[derive(Subcommand)]
+enum FolderCommand {
+    /// 显示收藏夹内的视频
+    Show {
+        media_id: i64,
+        /// 获取全部视频而不只是第一页
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PrivacyCommand {
+    /// 列出公开的收藏夹及示例标题
+    Audit,
+    /// 将收藏夹设为公开
+    Public {
+        #[arg(required = true)]
+        media_ids: Vec<i64>,
+    },
+    /// 将收藏夹设为私密
+    Private {
+        #[arg(required = true)]
+        media_ids: Vec<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotCommand {
+    /// 保存当前全部收藏夹的快照
+    Take,
+    /// 列出已保存的快照
+    List,
+    /// 比较两个快照，FROM 为较早的快照ID
+    Diff {
+        from: i64,
+        to: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// 保存配置，未指定的字段保持不变
+    Set {
+        #[arg(long)]
+        up_mid: Option<String>,
+        #[arg(long)]
+        csrf_token: Option<String>,
+        #[arg(long)]
+        cookie: Option<String>,
+        /// 代理地址（http://、https://、socks5://），传入空字符串清除
+        #[arg(long)]
+        proxy: Option<String>,
+        /// 连接超时（秒），0 表示不限制
+        #[arg(long)]
+        connect_timeout: Option<u64>,
+        /// 读取超时（秒），0 表示不限制
+        #[arg(long)]
+        read_timeout: Option<u64>,
+        /// 额外信任的 CA 证书文件（PEM），传入空字符串清除
+        #[arg(long)]
+        ca_bundle: Option<String>,
+        /// 只使用 IPv4 连接
+        #[arg(long)]
+        ipv4_only: Option<bool>,
+        /// 请求头预设：chrome_windows、chrome_macos、edge_windows、firefox_windows、safari_macos
+        #[arg(long)]
+        header_preset: Option<String>,
+        /// 自定义 User-Agent，会覆盖预设中的值
+        #[arg(long)]
+        user_agent: Option<String>,
+    },
+    /// 显示当前配置
+    Show,
+    /// 校验 Cookie 是否有效并显示账号信息
+    Verify,
+    /// 列出本机的 Firefox / Chromium 配置文件
+    Browsers,
+    /// 从本机浏览器导入登录 Cookie，不指定时依次尝试所有配置文件
+    ImportBrowser {
+        /// 浏览器的 Cookie 数据库路径
+        #[arg(long)]
+        cookie_path: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Serialize)]
+struct ExportFolder {
+    folder: FavoriteFolder,
+    resources: Vec<FavoriteResource>,
+}
+
+/// 终端显示宽度，中日韩字符按两列计算
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.len_utf8() > 1 { 2 } else { 1 }).sum()
+}
+
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(display_width(cell));
+        }
+    }
+
+    let format_row = |cells: Vec<String>| {
+        cells.iter().enumerate()
+            .map(|(i, cell)| format!("{}{}", cell, " ".repeat(widths[i] - display_width(cell))))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(headers.iter().map(|h| h.to_string()).collect()));
+    println!("{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("  "));
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("序列化输出失败: {}", e))?;
+    println!("{}", text);
+    Ok(())
+}
+
+fn format_duration(seconds: i32) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+fn print_folders(folders: &[FavoriteFolder]) {
+    print_table(
+        &["ID", "标题", "视频数", "私密"],
+        folders.iter().map(|f| vec![
+            f.id.to_string(),
+            f.title.clone(),
+            f.media_count.to_string(),
+            if f.is_private() { "是" } else { "否" }.to_string(),
+        ]).collect(),
+    );
+}
+
+fn print_resources(resources: &[FavoriteResource]) {
+    print_table(
+        &["ID", "BV号", "类型", "标题", "UP主", "时长", "状态"],
+        resources.iter().map(|r| vec![
+            r.id.to_string(),
+            r.bvid.clone(),
+            r.resource_type.name().to_string(),
+            r.title.clone(),
+            r.upper.as_ref().map(|u| u.name.clone()).unwrap_or_default(),
+            format_duration(r.duration),
+            if r.is_invalid() { "失效" } else { "正常" }.to_string(),
+        ]).collect(),
+    );
+}
+
+fn print_search_hits<'a>(hits: impl Iterator<Item = (&'a str, &'a FavoriteResource)>) {
+    print_table(
+        &["收藏夹", "BV号", "标题", "UP主"],
+        hits.map(|(folder_title, r)| vec![
+            folder_title.to_string(),
+            r.bvid.clone(),
+            r.title.clone(),
+            r.upper.as_ref().map(|u| u.name.clone()).unwrap_or_default(),
+        ]).collect(),
+    );
+}
+
+fn require_config() -> Result<AppConfig, String> {
+    load_config()?.ok_or_else(|| format!("请先配置Cookie（{}）", get_config_path().display()))
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let format = cli.format;
+
+    match cli.command {
+        Command::Folders { command: FoldersCommand::List } => {
+            let folders = app::get_favorites().await?;
+            match format {
+                OutputFormat::Json => print_json(&folders)?,
+                OutputFormat::Table => print_folders(&folders),
+            }
+        }
+        Command::Folder { command: FolderCommand::Show { media_id, all } } => {
+            let details = app::get_favorite_details(media_id).await?;
+            let resources = if all {
+                let config = require_config()?;
+                BilibiliClient::from_config(&config)?.get_favorite_videos(media_id, details.info.media_count, &config).await?
+            } else {
+                details.medias.clone().unwrap_or_default()
+            };
+            match format {
+                OutputFormat::Json => print_json(&ExportFolder { folder: details.info, resources })?,
+                OutputFormat::Table => {
+                    println!("{}（{}，共 {} 个视频）", details.info.title, details.info.id, details.info.media_count);
+                    print_resources(&resources);
+                }
+            }
+        }
+        Command::Clean { media_ids } => {
+            let media_ids = if media_ids.is_empty() {
+                app::get_favorites().await?.into_iter().map(|f| f.id).collect()
+            } else {
+                media_ids
+            };
+            let result = app::clean_multiple_favorites(media_ids).await?;
+            match format {
+                OutputFormat::Json => print_json(&result)?,
+                OutputFormat::Table => {
+                    print_table(
+                        &["收藏夹ID", "结果", "清理数量", "错误"],
+                        result.results.iter().map(|r| vec![
+                            r.media_id.to_string(),
+                            if r.success { "成功" } else { "失败" }.to_string(),
+                            r.cleaned_count.to_string(),
+                            r.error.clone().unwrap_or_default(),
+                        ]).collect(),
+                    );
+                    println!("共清理 {} 个失效内容", result.summary.total_cleaned);
+                }
+            }
+            if result.summary.failure > 0 {
+                return Err(format!("{} 个收藏夹清理失败", result.summary.failure));
+            }
+        }
+        Command::Check { media_ids } => {
+            let report = app::check_availability(media_ids).await?;
+            match format {
+                OutputFormat::Json => print_json(&report)?,
+                OutputFormat::Table => {
+                    print_table(
+                        &["收藏夹", "BV号", "标题", "UP主", "状态", "说明"],
+                        report.entries.iter()
+                            .filter(|e| e.status != Availability::Available)
+                            .map(|e| vec![
+                                e.folder_id.to_string(),
+                                e.bvid.clone(),
+                                e.title.clone(),
+                                e.upper.clone().unwrap_or_default(),
+                                e.status.name().to_string(),
+                                e.message.clone(),
+                            ])
+                            .collect(),
+                    );
+                    let counts: Vec<String> = report.counts.iter()
+                        .map(|(status, count)| format!("{} {}", status.name(), count))
+                        .collect();
+                    println!("\n{}", counts.join("，"));
+                }
+            }
+        }
+        Command::Move { source_id, target_id, video_ids, oldest_first } => {
+            let video_ids = if video_ids.is_empty() {
+                let direction = if oldest_first { MoveDirection::OldestFirst } else { MoveDirection::NewestFirst };
+                let plan = app::plan_move(source_id, target_id, MoveFilter::default(), direction).await?;
+                plan.videos.iter().map(|v| v.id).collect()
+            } else {
+                video_ids
+            };
+            let result = app::move_videos(source_id, target_id, video_ids).await?;
+            match format {
+                OutputFormat::Json => print_json(&result)?,
+                OutputFormat::Table => println!("{}", result.message.clone().unwrap_or_default()),
+            }
+            if result.fail_count > 0 {
+                return Err(format!("{} 个视频移动失败", result.fail_count));
+            }
+        }
+        Command::Merge { target_id, source_ids, delete_emptied } => {
+            let report = app::merge_folders(source_ids, target_id, delete_emptied).await?;
+            match format {
+                OutputFormat::Json => print_json(&report)?,
+                OutputFormat::Table => {
+                    print_table(
+                        &["ID", "收藏夹", "移动", "重复", "容量不足", "失败", "已删除"],
+                        report.sources.iter().map(|r| vec![
+                            r.folder_id.to_string(),
+                            r.title.clone(),
+                            r.moved_count.to_string(),
+                            r.duplicate_count.to_string(),
+                            r.skipped_count.to_string(),
+                            r.failed_count.to_string(),
+                            if r.deleted { "是" } else { "否" }.to_string(),
+                        ]).collect(),
+                    );
+                    println!("\n共移动 {} 个内容到「{}」", report.moved_count, report.target_title);
+                    for error in report.sources.iter().flat_map(|r| &r.errors) {
+                        eprintln!("{}", error);
+                    }
+                }
+            }
+        }
+        Command::Split { media_id, by, template, min_count, preview: true } => {
+            let preview = app::preview_split_folder(media_id, by.into(), template, Some(min_count)).await?;
+            match format {
+                OutputFormat::Json => print_json(&preview)?,
+                OutputFormat::Table => {
+                    println!("{}（共 {} 个内容）", preview.folder_title, preview.total);
+                    print_table(
+                        &["分组", "目标收藏夹", "数量", "说明"],
+                        preview.groups.iter().map(|g| vec![
+                            g.label.clone(),
+                            g.folder_title.clone(),
+                            g.count.to_string(),
+                            match (&g.error, g.stays, g.existing_folder_id) {
+                                (Some(error), _, _) => error.clone(),
+                                (None, true, _) => "留在原收藏夹".to_string(),
+                                (None, false, Some(_)) => "移动到已有收藏夹".to_string(),
+                                (None, false, None) => "新建".to_string(),
+                            },
+                        ]).collect(),
+                    );
+                }
+            }
+        }
+        Command::Split { media_id, by, template, min_count, preview: false } => {
+            let report = app::split_folder(media_id, by.into(), template, Some(min_count)).await?;
+            match format {
+                OutputFormat::Json => print_json(&report)?,
+                OutputFormat::Table => {
+                    print_table(
+                        &["分组", "收藏夹", "移动", "失败"],
+                        report.groups.iter().map(|g| vec![
+                            g.label.clone(),
+                            g.folder_title.clone(),
+                            g.moved_count.to_string(),
+                            g.failed_count.to_string(),
+                        ]).collect(),
+                    );
+                    for error in report.groups.iter().flat_map(|g| &g.errors) {
+                        eprintln!("{}", error);
+                    }
+                }
+            }
+        }
+        Command::Rename { media_ids, template, pattern, replace, preview } => {
+            let rule = match (template, pattern) {
+                (Some(template), _) => RenameRule::Template { template },
+                (None, pattern) => RenameRule::Regex {
+                    pattern: pattern.unwrap_or_default(),
+                    replacement: replace.unwrap_or_default(),
+                },
+            };
+
+            if preview {
+                let items = app::preview_rename_folders(media_ids, rule).await?;
+                match format {
+                    OutputFormat::Json => print_json(&items)?,
+                    OutputFormat::Table => print_table(
+                        &["ID", "原名称", "新名称", "说明"],
+                        items.iter().map(|i| vec![
+                            i.folder_id.to_string(),
+                            i.old_title.clone(),
+                            i.new_title.clone(),
+                            match (&i.error, i.changed) {
+                                (Some(error), _) => error.clone(),
+                                (None, true) => String::new(),
+                                (None, false) => "名称不变".to_string(),
+                            },
+                        ]).collect(),
+                    ),
+                }
+            } else {
+                let results = app::rename_folders(media_ids, rule).await?;
+                match format {
+                    OutputFormat::Json => print_json(&results)?,
+                    OutputFormat::Table => print_table(
+                        &["ID", "原名称", "新名称", "结果"],
+                        results.iter().map(|r| vec![
+                            r.folder_id.to_string(),
+                            r.old_title.clone(),
+                            r.new_title.clone(),
+                            match (&r.error, r.renamed) {
+                                (Some(error), _) => error.clone(),
+                                (None, true) => "已重命名".to_string(),
+                                (None, false) => "名称不变".to_string(),
+                            },
+                        ]).collect(),
+                    ),
+                }
+            }
+        }
+        Command::Sort { folder_ids } => {
+            let result = app::execute_sort(folder_ids).await?;
+            match format {
+                OutputFormat::Json => print_json(&result)?,
+                OutputFormat::Table => println!("{}", result.message),
+            }
+            if !result.success {
+                return Err(result.message);
+            }
+        }
+        Command::Search { keyword, local: true, .. } => {
+            let hits = app::search_favorites(keyword, None).await?;
+            match format {
+                OutputFormat::Json => print_json(&hits)?,
+                OutputFormat::Table => print_search_hits(hits.iter().map(|h| (h.folder_title.as_str(), &h.resource))),
+            }
+        }
+        Command::Search { keyword, folder, local: false } => {
+            let hits = app::search_favorites_online(keyword, folder).await?;
+            match format {
+                OutputFormat::Json => print_json(&hits)?,
+                OutputFormat::Table => print_search_hits(hits.iter().map(|h| (h.folder_title.as_str(), &h.resource))),
+            }
+        }
+        Command::Export { output } => {
+            let config = require_config()?;
+            let client = BilibiliClient::from_config(&config)?;
+            let mut export = Vec::new();
+            for folder in client.get_favorite_folders(&config).await? {
+                let resources = client.get_favorite_videos(folder.id, folder.media_count, &config).await?;
+                export.push(ExportFolder { folder, resources });
+            }
+            let text = serde_json::to_string_pretty(&export)
+                .map_err(|e| format!("序列化输出失败: {}", e))?;
+            match output {
+                Some(path) => std::fs::write(&path, text)
+                    .map_err(|e| format!("写入导出文件失败: {}", e))?,
+                None => println!("{}", text),
+            }
+        }
+        Command::Privacy { command: PrivacyCommand::Audit } => {
+            let audit = app::audit_folder_privacy(None).await?;
+            match format {
+                OutputFormat::Json => print_json(&audit)?,
+                OutputFormat::Table => {
+                    print_table(
+                        &["ID", "收藏夹", "数量", "示例"],
+                        audit.public_folders.iter().map(|f| vec![
+                            f.folder_id.to_string(),
+                            f.title.clone(),
+                            f.media_count.to_string(),
+                            f.sample_titles.join(" / "),
+                        ]).collect(),
+                    );
+                    println!("\n{} 个公开收藏夹共 {} 个内容，{} 个私密收藏夹", audit.public_count, audit.exposed_count, audit.private_count);
+                }
+            }
+        }
+        Command::Privacy { command } => {
+            let (media_ids, private) = match command {
+                PrivacyCommand::Public { media_ids } => (media_ids, false),
+                PrivacyCommand::Private { media_ids } => (media_ids, true),
+                PrivacyCommand::Audit => unreachable!(),
+            };
+            let results = app::set_folders_privacy(media_ids, private).await?;
+            match format {
+                OutputFormat::Json => print_json(&results)?,
+                OutputFormat::Table => print_table(
+                    &["ID", "收藏夹", "结果"],
+                    results.iter().map(|r| vec![
+                        r.folder_id.to_string(),
+                        r.title.clone(),
+                        match (&r.error, r.changed) {
+                            (Some(error), _) => error.clone(),
+                            (None, true) => "已修改".to_string(),
+                            (None, false) => "无需修改".to_string(),
+                        },
+                    ]).collect(),
+                ),
+            }
+        }
+        Command::Snapshot { command: SnapshotCommand::Take } => {
+            let summary = app::take_snapshot().await?;
+            match format {
+                OutputFormat::Json => print_json(&summary)?,
+                OutputFormat::Table => println!("已保存快照 {}（{} 个收藏夹，{} 个内容）", summary.id, summary.folder_count, summary.resource_count),
+            }
+        }
+        Command::Snapshot { command: SnapshotCommand::List } => {
+            let snapshots = app::list_snapshots().await?;
+            match format {
+                OutputFormat::Json => print_json(&snapshots)?,
+                OutputFormat::Table => print_table(
+                    &["ID", "时间", "收藏夹数", "内容数"],
+                    snapshots.iter().map(|s| vec![
+                        s.id.to_string(),
+                        chrono::DateTime::from_timestamp(s.id, 0)
+                            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default(),
+                        s.folder_count.to_string(),
+                        s.resource_count.to_string(),
+                    ]).collect(),
+                ),
+            }
+        }
+        Command::Snapshot { command: SnapshotCommand::Diff { from, to } } => {
+            let diff = app::diff_snapshots(from, to).await?;
+            match format {
+                OutputFormat::Json => print_json(&diff)?,
+                OutputFormat::Table => {
+                    let folders = |ids: &[i64]| ids.iter()
+                        .map(|id| diff.folder_titles.get(id).cloned().unwrap_or_else(|| id.to_string()))
+                        .collect::<Vec<_>>()
+                        .join("、");
+                    let mut rows = Vec::new();
+                    for (change, items) in [("新增", &diff.added), ("删除", &diff.removed), ("失效", &diff.invalidated)] {
+                        rows.extend(items.iter().map(|item| vec![
+                            change.to_string(),
+                            folders(&item.folder_ids),
+                            item.resource.bvid.clone(),
+                            item.resource.title.clone(),
+                        ]));
+                    }
+                    rows.extend(diff.moved.iter().map(|item| vec![
+                        "移动".to_string(),
+                        format!("{} → {}", folders(&item.from_folder_ids), folders(&item.to_folder_ids)),
+                        item.resource.bvid.clone(),
+                        item.resource.title.clone(),
+                    ]));
+                    rows.extend(diff.retitled.iter().map(|item| vec![
+                        "改名".to_string(),
+                        folders(&item.folder_ids),
+                        item.resource.bvid.clone(),
+                        format!("{} → {}", item.old_title, item.resource.title),
+                    ]));
+                    print_table(&["变化", "收藏夹", "BV号", "标题"], rows);
+                }
+            }
+        }
+        Command::Config { command: ConfigCommand::Set { up_mid, csrf_token, cookie, proxy, connect_timeout, read_timeout, ca_bundle, ipv4_only, header_preset, user_agent } } => {
+            let mut config = load_config()?.unwrap_or_default();
+            if let Some(up_mid) = up_mid {
+                config.up_mid = up_mid;
+            }
+            if let Some(csrf_token) = csrf_token {
+                config.csrf_token = csrf_token;
+            }
+            if let Some(cookie) = cookie {
+                config.cookie = cookie;
+            }
+            if let Some(proxy) = proxy {
+                config.network.proxy = Some(proxy).filter(|p| !p.is_empty());
+            }
+            if let Some(secs) = connect_timeout {
+                config.network.connect_timeout = Some(secs).filter(|s| *s > 0);
+            }
+            if let Some(secs) = read_timeout {
+                config.network.read_timeout = Some(secs).filter(|s| *s > 0);
+            }
+            if let Some(ca_bundle) = ca_bundle {
+                config.network.ca_bundle = Some(ca_bundle).filter(|p| !p.is_empty());
+            }
+            if let Some(ipv4_only) = ipv4_only {
+                config.network.ipv4_only = ipv4_only;
+            }
+            if let Some(name) = header_preset {
+                config.headers = HeaderProfile::preset(&name)
+                    .ok_or_else(|| format!("未知的请求头预设: {}", name))?;
+            }
+            if let Some(user_agent) = user_agent {
+                config.headers.user_agent = user_agent;
+                config.headers.name = "custom".to_string();
+            }
+            app::save_config_command(config).await?;
+            println!("配置已保存到 {}", get_config_path().display());
+        }
+        Command::Config { command: ConfigCommand::Verify } => {
+            let account = app::verify_account(None).await?;
+            match format {
+                OutputFormat::Json => print_json(&account)?,
+                OutputFormat::Table => print_table(
+                    &["字段", "值"],
+                    vec![
+                        vec!["mid".to_string(), account.mid.to_string()],
+                        vec!["用户名".to_string(), account.name],
+                        vec!["等级".to_string(), account.level.to_string()],
+                        vec!["大会员".to_string(), account.vip_label.unwrap_or_else(|| if account.is_vip { "是" } else { "否" }.to_string())],
+                    ],
+                ),
+            }
+        }
+        Command::Config { command: ConfigCommand::Browsers } => {
+            let profiles = browser::find_profiles();
+            match format {
+                OutputFormat::Json => print_json(&profiles)?,
+                OutputFormat::Table => print_table(
+                    &["浏览器", "配置文件", "Cookie数据库"],
+                    profiles.iter().map(|p| vec![
+                        p.browser.clone(),
+                        p.profile.clone(),
+                        p.cookie_path.display().to_string(),
+                    ]).collect(),
+                ),
+            }
+        }
+        Command::Config { command: ConfigCommand::ImportBrowser { cookie_path } } => {
+            let profiles: Vec<_> = browser::find_profiles().into_iter()
+                .filter(|p| cookie_path.as_ref().is_none_or(|path| &p.cookie_path == path))
+                .collect();
+            let (profile, mut config) = browser::import_config(&profiles)?;
+            save_login_config(&mut config)?;
+            println!("已从 {}（{}）导入账号 {} 的登录信息", profile.browser, profile.profile, config.up_mid);
+        }
+        Command::Config { command: ConfigCommand::Show } => {
+            let mut config = require_config()?;
+            // Cookie 和 csrf_token 可直接用于登录，两种格式都不输出原文
+            config.cookie = format!("{} 个字符", config.cookie.chars().count());
+            config.csrf_token = format!("{} 个字符", config.csrf_token.chars().count());
+            // 代理地址中可能带有账号密码
+            config.network.proxy = config.network.masked_proxy();
+            match format {
+                OutputFormat::Json => print_json(&config)?,
+                OutputFormat::Table => print_table(
+                    &["字段", "值"],
+                    vec![
+                        vec!["up_mid".to_string(), config.up_mid],
+                        vec!["csrf_token".to_string(), config.csrf_token],
+                        vec!["cookie".to_string(), config.cookie],
+                        vec!["proxy".to_string(), config.network.proxy.unwrap_or_default()],
+                        vec!["connect_timeout".to_string(), config.network.connect_timeout.map(|s| format!("{}s", s)).unwrap_or_default()],
+                        vec!["read_timeout".to_string(), config.network.read_timeout.map(|s| format!("{}s", s)).unwrap_or_default()],
+                        vec!["ca_bundle".to_string(), config.network.ca_bundle.unwrap_or_default()],
+                        vec!["ipv4_only".to_string(), config.network.ipv4_only.to_string()],
+                        vec!["header_preset".to_string(), config.headers.name],
+                        vec!["user_agent".to_string(), config.headers.user_agent],
+                    ],
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(error) = run(Cli::parse()).await {
+        eprintln!("错误: {}", error);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,16 @@
+//! 本地 HTTP 接口服务，可替代 `new/backend` 的 Node 后端
+//!
+//! 通过 `HOST` 和 `PORT` 环境变量指定监听地址，默认为 `localhost:8080`
+
+use bilibili_favorites_tools_lib::server;
+
+#[tokio::main]
+async fn main() {
+    let host = std::env::var("HOST").unwrap_or_else(|_| "localhost".to_string());
+    let port = std::env::var("PORT").unwrap_or_else(|_| "8080".to_string());
+
+    if let Err(error) = server::serve(&format!("{}:{}", host, port)).await {
+        eprintln!("错误: {}", error);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,446 @@
+//! 从本机浏览器的 Cookie 数据库中读取 B站 登录信息（Linux）
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use rusqlite::{Connection, OpenFlags};
+use crate::config::AppConfig;
+use crate::cookies::{config_from_cookies, Cookie};
+
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+/// 没有可用的密钥环时 Chromium 在 Linux 上使用的固定密码
+const CHROMIUM_FALLBACK_PASSWORD: &[u8] = b"peanuts";
+const CHROMIUM_SALT: &[u8] = b"saltysalt";
+const CHROMIUM_IV: [u8; 16] = [b' '; 16];
+
+/// Chromium 的时间从 1601-01-01 开始计算，单位为微秒
+const CHROMIUM_EPOCH_OFFSET: i64 = 11_644_473_600;
+
+/// Cookie 数据库版本 24 起，解密后的值前带有 32 字节的域名 SHA256
+const CHROMIUM_HASH_PREFIX_VERSION: i64 = 24;
+
+/// Chromium 系浏览器在 `~/.config` 下的目录
+const CHROMIUM_BROWSERS: &[(&str, &str)] = &[
+    ("Google Chrome", "google-chrome"),
+    ("Chromium", "chromium"),
+    ("Microsoft Edge", "microsoft-edge"),
+    ("Brave", "BraveSoftware/Brave-Browser"),
+    ("Vivaldi", "vivaldi"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserKind {
+    Firefox,
+    Chromium,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserProfile {
+    pub kind: BrowserKind,
+    /// 浏览器名称，如 Firefox、Google Chrome
+    pub browser: String,
+    /// 配置文件目录名
+    pub profile: String,
+    pub cookie_path: PathBuf,
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir).into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn profile_name(dir: &Path) -> String {
+    dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// 在指定的家目录下查找浏览器配置文件
+pub fn find_profiles_in(home: &Path) -> Vec<BrowserProfile> {
+    let mut profiles = Vec::new();
+
+    // Snap 安装的 Firefox 数据位于 ~/snap 下
+    for root in [home.join(".mozilla/firefox"), home.join("snap/firefox/common/.mozilla/firefox")] {
+        for dir in subdirectories(&root) {
+            let cookie_path = dir.join("cookies.sqlite");
+            if cookie_path.is_file() {
+                profiles.push(BrowserProfile {
+                    kind: BrowserKind::Firefox,
+                    browser: "Firefox".to_string(),
+                    profile: profile_name(&dir),
+                    cookie_path,
+                });
+            }
+        }
+    }
+
+    for (browser, config_dir) in CHROMIUM_BROWSERS {
+        for dir in subdirectories(&home.join(".config").join(config_dir)) {
+            // 新版本的 Cookie 数据库移到了 Network 目录下
+            let cookie_path = [dir.join("Network/Cookies"), dir.join("Cookies")].into_iter()
+                .find(|path| path.is_file());
+            if let Some(cookie_path) = cookie_path {
+                profiles.push(BrowserProfile {
+                    kind: BrowserKind::Chromium,
+                    browser: browser.to_string(),
+                    profile: profile_name(&dir),
+                    cookie_path,
+                });
+            }
+        }
+    }
+
+    profiles
+}
+
+pub fn find_profiles() -> Vec<BrowserProfile> {
+    match dirs::home_dir() {
+        Some(home) => find_profiles_in(&home),
+        None => Vec::new(),
+    }
+}
+
+/// 浏览器运行时数据库会被锁定，先复制一份（连同 WAL 文件）再读取
+fn open_copy(path: &Path) -> Result<(Connection, PathBuf), String> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let temp_dir = std::env::temp_dir().join(format!("bilibili-fav-cookies-{}-{}", std::process::id(), nanos));
+    fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("创建临时目录失败: {}", e))?;
+
+    let copy = temp_dir.join("cookies.sqlite");
+    fs::copy(path, &copy)
+        .map_err(|e| format!("复制Cookie数据库失败: {}", e))?;
+    for suffix in ["-wal", "-shm"] {
+        let mut side = path.as_os_str().to_owned();
+        side.push(suffix);
+        let side = PathBuf::from(side);
+        if side.is_file() {
+            let _ = fs::copy(&side, temp_dir.join(format!("cookies.sqlite{}", suffix)));
+        }
+    }
+
+    let connection = Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .map_err(|e| format!("打开Cookie数据库失败: {}", e))?;
+    Ok((connection, temp_dir))
+}
+
+fn read_firefox(connection: &Connection) -> Result<Vec<Cookie>, String> {
+    let mut statement = connection.prepare(
+        "SELECT name, value, host, path, expiry, isSecure, isHttpOnly, sameSite \
+         FROM moz_cookies WHERE host LIKE '%bilibili.com'"
+    ).map_err(|e| format!("读取Firefox Cookie失败: {}", e))?;
+
+    let rows = statement.query_map([], |row| {
+        let expiry: i64 = row.get(4)?;
+        let same_site: i64 = row.get(7)?;
+        Ok(Cookie {
+            name: row.get(0)?,
+            value: row.get(1)?,
+            domain: row.get(2)?,
+            path: row.get(3)?,
+            // 新版本 Firefox 的 expiry 以毫秒为单位
+            expires: match expiry {
+                e if e <= 0 => None,
+                e if e > 100_000_000_000 => Some(e / 1000),
+                e => Some(e),
+            },
+            secure: row.get::<_, i64>(5)? != 0,
+            http_only: row.get::<_, i64>(6)? != 0,
+            same_site: match same_site {
+                1 => Some("Lax".to_string()),
+                2 => Some("Strict".to_string()),
+                _ => None,
+            },
+        })
+    }).map_err(|e| format!("读取Firefox Cookie失败: {}", e))?;
+
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("读取Firefox Cookie失败: {}", e))
+}
+
+fn chromium_key(password: &[u8]) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password, CHROMIUM_SALT, 1, &mut key);
+    key
+}
+
+/// 解密 Chromium 的 v10/v11 加密值
+///
+/// v10 使用固定密码；v11 的密码本应来自系统密钥环，这里只尝试固定密码和空密码，
+/// 对应未启用密钥环（`--password-store=basic`）时的情况。
+pub fn decrypt_chromium_value(encrypted: &[u8], strip_hash_prefix: bool) -> Result<String, String> {
+    let (version, data) = encrypted.split_at(encrypted.len().min(3));
+    let passwords: &[&[u8]] = match version {
+        b"v10" => &[CHROMIUM_FALLBACK_PASSWORD],
+        b"v11" => &[CHROMIUM_FALLBACK_PASSWORD, b""],
+        _ => return Err("不支持的Cookie加密格式".to_string()),
+    };
+
+    let mut plain = passwords.iter()
+        .find_map(|password| {
+            Aes128CbcDec::new(&chromium_key(password).into(), &CHROMIUM_IV.into())
+                .decrypt_padded_vec_mut::<Pkcs7>(data)
+                .ok()
+        })
+        .ok_or_else(|| "解密Cookie失败，可能使用了系统密钥环中的密码".to_string())?;
+
+    if strip_hash_prefix && plain.len() >= 32 {
+        plain.drain(..32);
+    }
+
+    String::from_utf8(plain).map_err(|_| "解密后的Cookie不是有效的文本".to_string())
+}
+
+fn read_chromium(connection: &Connection) -> Result<Vec<Cookie>, String> {
+    let db_version: i64 = connection
+        .query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| row.get::<_, String>(0))
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let strip_hash_prefix = db_version >= CHROMIUM_HASH_PREFIX_VERSION;
+
+    let mut statement = connection.prepare(
+        "SELECT name, value, encrypted_value, host_key, path, expires_utc, is_secure, is_httponly, samesite \
+         FROM cookies WHERE host_key LIKE '%bilibili.com'"
+    ).map_err(|e| format!("读取Chromium Cookie失败: {}", e))?;
+
+    let rows = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Vec<u8>>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, i64>(6)?,
+            row.get::<_, i64>(7)?,
+            row.get::<_, i64>(8)?,
+        ))
+    }).map_err(|e| format!("读取Chromium Cookie失败: {}", e))?;
+
+    let mut cookies = Vec::new();
+    for row in rows {
+        let (name, value, encrypted, domain, path, expires_utc, secure, http_only, same_site) =
+            row.map_err(|e| format!("读取Chromium Cookie失败: {}", e))?;
+
+        let value = if encrypted.is_empty() {
+            value
+        } else {
+            decrypt_chromium_value(&encrypted, strip_hash_prefix)
+                .map_err(|e| format!("{}: {}", name, e))?
+        };
+
+        cookies.push(Cookie {
+            name,
+            value,
+            domain,
+            path,
+            expires: if expires_utc > 0 { Some(expires_utc / 1_000_000 - CHROMIUM_EPOCH_OFFSET) } else { None },
+            secure: secure != 0,
+            http_only: http_only != 0,
+            same_site: match same_site {
+                0 => Some("None".to_string()),
+                1 => Some("Lax".to_string()),
+                2 => Some("Strict".to_string()),
+                _ => None,
+            },
+        });
+    }
+
+    Ok(cookies)
+}
+
+/// 读取配置文件中 B站 域名下的 Cookie
+pub fn read_cookies(profile: &BrowserProfile) -> Result<Vec<Cookie>, String> {
+    let (connection, temp_dir) = open_copy(&profile.cookie_path)?;
+    let cookies = match profile.kind {
+        BrowserKind::Firefox => read_firefox(&connection),
+        BrowserKind::Chromium => read_chromium(&connection),
+    };
+    drop(connection);
+    let _ = fs::remove_dir_all(temp_dir);
+    cookies
+}
+
+/// 从浏览器导入配置，未指定配置文件时依次尝试找到的所有配置文件
+pub fn import_config(profiles: &[BrowserProfile]) -> Result<(BrowserProfile, AppConfig), String> {
+    if profiles.is_empty() {
+        return Err("未找到Firefox或Chromium的配置文件".to_string());
+    }
+
+    let mut errors = Vec::new();
+    for profile in profiles {
+        match read_cookies(profile).and_then(|cookies| config_from_cookies(&cookies)) {
+            Ok(config) => return Ok((profile.clone(), config)),
+            Err(error) => errors.push(format!("{} ({}): {}", profile.browser, profile.profile, error)),
+        }
+    }
+
+    Err(format!("没有找到已登录B站的浏览器配置文件\n{}", errors.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::BlockEncryptMut;
+
+    type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
+
+    const SESSDATA: &str = "abc%2C1893456000%2Cdef";
+
+    fn temp_home(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bilibili-fav-browser-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn encrypt(version: &str, password: &[u8], plain: &[u8]) -> Vec<u8> {
+        let data = Aes128CbcEnc::new(&chromium_key(password).into(), &CHROMIUM_IV.into())
+            .encrypt_padded_vec_mut::<Pkcs7>(plain);
+        [version.as_bytes(), &data].concat()
+    }
+
+    fn create_firefox_fixture(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let connection = Connection::open(path).unwrap();
+        connection.execute_batch(
+            "CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, originAttributes TEXT NOT NULL DEFAULT '', \
+             name TEXT, value TEXT, host TEXT, path TEXT, expiry INTEGER, lastAccessed INTEGER, \
+             creationTime INTEGER, isSecure INTEGER, isHttpOnly INTEGER, inBrowserElement INTEGER DEFAULT 0, \
+             sameSite INTEGER DEFAULT 0, rawSameSite INTEGER DEFAULT 0, schemeMap INTEGER DEFAULT 0);"
+        ).unwrap();
+        let rows: [(&str, &str, &str, i64, i64); 4] = [
+            ("SESSDATA", SESSDATA, ".bilibili.com", 1_893_456_000, 1),
+            ("bili_jct", "csrf123", ".bilibili.com", 1_893_456_000_000, 0),
+            ("DedeUserID", "42", ".bilibili.com", 1_893_456_000, 0),
+            ("session", "other-site", ".example.com", 1_893_456_000, 0),
+        ];
+        for (name, value, host, expiry, http_only) in rows {
+            connection.execute(
+                "INSERT INTO moz_cookies (name, value, host, path, expiry, lastAccessed, creationTime, isSecure, isHttpOnly, sameSite) \
+                 VALUES (?1, ?2, ?3, '/', ?4, 0, 0, 1, ?5, 1)",
+                rusqlite::params![name, value, host, expiry, http_only],
+            ).unwrap();
+        }
+    }
+
+    fn create_chromium_fixture(path: &Path, db_version: i64, rows: &[(&str, &str, Vec<u8>)]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let connection = Connection::open(path).unwrap();
+        connection.execute_batch(
+            "CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR); \
+             CREATE TABLE cookies (creation_utc INTEGER NOT NULL, host_key TEXT NOT NULL, top_frame_site_key TEXT NOT NULL DEFAULT '', \
+             name TEXT NOT NULL, value TEXT NOT NULL, encrypted_value BLOB NOT NULL DEFAULT '', path TEXT NOT NULL, \
+             expires_utc INTEGER NOT NULL, is_secure INTEGER NOT NULL, is_httponly INTEGER NOT NULL, \
+             last_access_utc INTEGER NOT NULL DEFAULT 0, has_expires INTEGER NOT NULL DEFAULT 1, \
+             is_persistent INTEGER NOT NULL DEFAULT 1, priority INTEGER NOT NULL DEFAULT 1, samesite INTEGER NOT NULL DEFAULT -1);"
+        ).unwrap();
+        connection.execute("INSERT INTO meta (key, value) VALUES ('version', ?1)", [db_version.to_string()]).unwrap();
+        for (host, name, encrypted) in rows {
+            let expires_utc = (1_893_456_000 + CHROMIUM_EPOCH_OFFSET) * 1_000_000;
+            connection.execute(
+                "INSERT INTO cookies (creation_utc, host_key, name, value, encrypted_value, path, expires_utc, is_secure, is_httponly, samesite) \
+                 VALUES (0, ?1, ?2, '', ?3, '/', ?4, 1, 1, 1)",
+                rusqlite::params![host, name, encrypted, expires_utc],
+            ).unwrap();
+        }
+    }
+
+    #[test]
+    fn finds_firefox_and_chromium_profiles() {
+        let home = temp_home("discover");
+        create_firefox_fixture(&home.join(".mozilla/firefox/abcd.default-release/cookies.sqlite"));
+        create_chromium_fixture(&home.join(".config/chromium/Default/Cookies"), 18, &[]);
+        create_chromium_fixture(&home.join(".config/google-chrome/Profile 1/Network/Cookies"), 24, &[]);
+        fs::create_dir_all(home.join(".config/chromium/Crashpad")).unwrap();
+
+        let profiles = find_profiles_in(&home);
+        let found: Vec<(BrowserKind, &str, &str)> = profiles.iter()
+            .map(|p| (p.kind, p.browser.as_str(), p.profile.as_str()))
+            .collect();
+        assert_eq!(found, vec![
+            (BrowserKind::Firefox, "Firefox", "abcd.default-release"),
+            (BrowserKind::Chromium, "Google Chrome", "Profile 1"),
+            (BrowserKind::Chromium, "Chromium", "Default"),
+        ]);
+
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn imports_config_from_firefox() {
+        let home = temp_home("firefox");
+        create_firefox_fixture(&home.join(".mozilla/firefox/abcd.default/cookies.sqlite"));
+
+        let profiles = find_profiles_in(&home);
+        let cookies = read_cookies(&profiles[0]).unwrap();
+        assert_eq!(cookies.len(), 3);
+        assert!(cookies.iter().all(|c| c.expires == Some(1_893_456_000)));
+        assert!(cookies.iter().find(|c| c.name == "SESSDATA").unwrap().http_only);
+
+        let (_, config) = import_config(&profiles).unwrap();
+        assert_eq!(config.up_mid, "42");
+        assert_eq!(config.csrf_token, "csrf123");
+        assert_eq!(config.cookie, format!("SESSDATA={}; bili_jct=csrf123; DedeUserID=42", SESSDATA));
+
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn decrypts_chromium_v10_and_v11_values() {
+        let home = temp_home("chromium");
+        let prefixed = |value: &str| [[7u8; 32].as_slice(), value.as_bytes()].concat();
+        create_chromium_fixture(&home.join(".config/chromium/Default/Cookies"), 24, &[
+            (".bilibili.com", "SESSDATA", encrypt("v10", CHROMIUM_FALLBACK_PASSWORD, &prefixed(SESSDATA))),
+            (".bilibili.com", "bili_jct", encrypt("v11", CHROMIUM_FALLBACK_PASSWORD, &prefixed("csrf123"))),
+            (".bilibili.com", "DedeUserID", encrypt("v11", b"", &prefixed("42"))),
+            (".example.com", "other", encrypt("v10", CHROMIUM_FALLBACK_PASSWORD, &prefixed("x"))),
+        ]);
+
+        let profiles = find_profiles_in(&home);
+        let cookies = read_cookies(&profiles[0]).unwrap();
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies[0].expires, Some(1_893_456_000));
+        assert_eq!(cookies[0].same_site.as_deref(), Some("Lax"));
+
+        let (profile, config) = import_config(&profiles).unwrap();
+        assert_eq!(profile.browser, "Chromium");
+        assert_eq!(config.up_mid, "42");
+        assert_eq!(config.csrf_token, "csrf123");
+        assert!(config.cookie.starts_with(&format!("SESSDATA={}", SESSDATA)));
+
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn keeps_value_without_hash_prefix_for_old_databases() {
+        let encrypted = encrypt("v10", CHROMIUM_FALLBACK_PASSWORD, b"csrf123");
+        assert_eq!(decrypt_chromium_value(&encrypted, false).unwrap(), "csrf123");
+        assert!(decrypt_chromium_value(b"v20abcdef", false).is_err());
+    }
+
+    #[test]
+    fn reports_profiles_without_login() {
+        let home = temp_home("no-login");
+        create_chromium_fixture(&home.join(".config/chromium/Default/Cookies"), 18, &[
+            (".bilibili.com", "buvid3", encrypt("v10", CHROMIUM_FALLBACK_PASSWORD, b"visitor")),
+        ]);
+
+        let error = import_config(&find_profiles_in(&home)).unwrap_err();
+        assert!(error.contains("SESSDATA"), "{}", error);
+        assert!(import_config(&[]).is_err());
+
+        fs::remove_dir_all(home).unwrap();
+    }
+}
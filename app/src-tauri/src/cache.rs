@@ -0,0 +1,166 @@
+//! 收藏夹和内容的本地缓存，按账号存放在 `~/.bilibili-fav-cleaner/cache/<up_mid>/` 下
+//!
+//! 缓存只用于加速，写入失败时会删除对应的旧文件并返回错误，调用方可以忽略错误继续使用接口返回的数据。
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use chrono::Local;
+use crate::bilibili::{FavoriteFolder, FavoriteResource};
+use crate::config::get_config_dir;
+use crate::search::SearchIndex;
+
+/// 缓存有效期（秒），过期后即使收藏夹的 mtime 未变化也重新获取
+pub const CACHE_TTL: i64 = 10 * 60;
+
+const FOLDERS_FILE: &str = "folders.json";
+const INDEX_FILE: &str = "search-index.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFolders {
+    pub fetched_at: i64,
+    pub folders: Vec<FavoriteFolder>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResources {
+    pub fetched_at: i64,
+    pub folder: FavoriteFolder,
+    pub resources: Vec<FavoriteResource>,
+    /// 是否为完整列表，否则只有第一页
+    pub complete: bool,
+}
+
+pub fn is_fresh(fetched_at: i64) -> bool {
+    Local::now().timestamp() - fetched_at < CACHE_TTL
+}
+
+pub struct FavoritesCache {
+    dir: PathBuf,
+}
+
+impl FavoritesCache {
+    pub fn new(up_mid: &str) -> Self {
+        Self { dir: get_config_dir().join("cache").join(up_mid) }
+    }
+
+    fn resources_file(media_id: i64) -> String {
+        format!("folder-{}.json", media_id)
+    }
+
+    /// 缓存文件损坏时视为没有缓存
+    fn read<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let content = fs::read_to_string(self.dir.join(name)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 写入失败时删除旧的缓存文件，避免之后读到过时的内容
+    fn write<T: Serialize>(&self, name: &str, value: &T) -> Result<(), String> {
+        let result = fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("创建缓存目录失败: {}", e))
+            .and_then(|_| serde_json::to_string(value).map_err(|e| format!("序列化缓存失败: {}", e)))
+            .and_then(|content| fs::write(self.dir.join(name), content).map_err(|e| format!("写入缓存失败: {}", e)));
+
+        if result.is_err() {
+            self.remove(name);
+        }
+        result
+    }
+
+    fn remove(&self, name: &str) {
+        let _ = fs::remove_file(self.dir.join(name));
+    }
+
+    pub fn folders(&self) -> Option<CachedFolders> {
+        self.read(FOLDERS_FILE)
+    }
+
+    /// 保存收藏夹列表，并丢弃 mtime 或数量发生变化、或已被删除的收藏夹的内容缓存
+    pub fn save_folders(&self, folders: &[FavoriteFolder]) -> Result<(), String> {
+        for cached in self.all_resources() {
+            let unchanged = folders.iter().any(|f| {
+                f.id == cached.folder.id && f.mtime == cached.folder.mtime && f.media_count == cached.folder.media_count
+            });
+            if !unchanged {
+                self.remove(&Self::resources_file(cached.folder.id));
+            }
+        }
+
+        self.write(FOLDERS_FILE, &CachedFolders {
+            fetched_at: Local::now().timestamp(),
+            folders: folders.to_vec(),
+        })?;
+        self.update_index(|index| index.sync_folders(folders))
+    }
+
+    pub fn resources(&self, media_id: i64) -> Option<CachedResources> {
+        self.read(&Self::resources_file(media_id))
+    }
+
+    /// 保存收藏夹内容，只有完整列表才会替换搜索索引中该收藏夹的内容
+    pub fn save_resources(&self, folder: &FavoriteFolder, resources: &[FavoriteResource], complete: bool) -> Result<(), String> {
+        self.write(&Self::resources_file(folder.id), &CachedResources {
+            fetched_at: Local::now().timestamp(),
+            folder: folder.clone(),
+            resources: resources.to_vec(),
+            complete,
+        })?;
+        if complete {
+            self.update_index(|index| index.update_folder(folder, resources))?;
+        }
+        Ok(())
+    }
+
+    /// 所有已缓存的收藏夹内容
+    pub fn all_resources(&self) -> Vec<CachedResources> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        let mut cached: Vec<CachedResources> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("folder-"))
+            .filter_map(|name| self.read(&name))
+            .collect();
+        cached.sort_by_key(|c| c.folder.id);
+        cached
+    }
+
+    /// 搜索索引，尚未建立时根据已缓存的内容生成
+    pub fn index(&self) -> SearchIndex {
+        let mut index = SearchIndex::open(self.dir.join(INDEX_FILE));
+        if !index.exists() {
+            for cached in self.all_resources().into_iter().filter(|c| c.complete) {
+                index.update_folder(&cached.folder, &cached.resources);
+            }
+        }
+        index
+    }
+
+    /// 修改搜索索引并保存
+    pub fn update_index(&self, update: impl FnOnce(&mut SearchIndex)) -> Result<(), String> {
+        let mut index = self.index();
+        update(&mut index);
+        index.save()
+    }
+
+    /// 收藏夹内容发生变化后调用，同时丢弃收藏夹列表（其中的数量已过时）
+    ///
+    /// 搜索索引不受影响，需要由调用方通过 `update_index` 同步具体的变化。
+    pub fn invalidate(&self, media_ids: &[i64]) {
+        for media_id in media_ids {
+            self.remove(&Self::resources_file(*media_id));
+        }
+        self.remove(FOLDERS_FILE);
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)
+                .map_err(|e| format!("清除缓存失败: {}", e))?;
+        }
+        Ok(())
+    }
+}
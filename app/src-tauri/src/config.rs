@@ -2,12 +2,20 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use dirs::home_dir;
+use crate::headers::HeaderProfile;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub up_mid: String,
     pub csrf_token: String,
     pub cookie: String,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub headers: HeaderProfile,
+    /// 离线模式下只读取本地缓存，不访问网络
+    #[serde(default)]
+    pub offline: bool,
 }
 
 impl Default for AppConfig {
@@ -16,17 +24,54 @@ impl Default for AppConfig {
             up_mid: String::new(),
             csrf_token: String::new(),
             cookie: String::new(),
+            network: NetworkConfig::default(),
+            headers: HeaderProfile::default(),
+            offline: false,
         }
     }
 }
 
-pub fn get_config_path() -> PathBuf {
+/// 网络设置，未填写的项使用 reqwest 的默认行为
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// 代理地址，支持 http://、https://、socks5:// 和 socks5h://
+    pub proxy: Option<String>,
+    /// 连接超时（秒）
+    pub connect_timeout: Option<u64>,
+    /// 读取超时（秒）
+    pub read_timeout: Option<u64>,
+    /// 额外信任的 CA 证书文件（PEM，可包含多个证书）
+    pub ca_bundle: Option<String>,
+    /// 只使用 IPv4 连接
+    pub ipv4_only: bool,
+}
+
+impl NetworkConfig {
+    /// 用于展示的代理地址，其中的用户名和密码替换为 ***
+    pub fn masked_proxy(&self) -> Option<String> {
+        let proxy = self.proxy.as_deref()?;
+        let authority_start = proxy.find("://").map(|i| i + 3).unwrap_or(0);
+        let authority_end = proxy[authority_start..].find(['/', '?', '#'])
+            .map(|i| authority_start + i)
+            .unwrap_or(proxy.len());
+        Some(match proxy[authority_start..authority_end].rfind('@') {
+            Some(at) => format!("{}***{}", &proxy[..authority_start], &proxy[authority_start + at..]),
+            None => proxy.to_string(),
+        })
+    }
+}
+
+pub fn get_config_dir() -> PathBuf {
     let mut path = home_dir().unwrap_or_else(|| PathBuf::from("."));
     path.push(".bilibili-fav-cleaner");
-    path.push("config.json");
     path
 }
 
+pub fn get_config_path() -> PathBuf {
+    get_config_dir().join("config.json")
+}
+
 pub fn load_config() -> Result<Option<AppConfig>, String> {
     let config_path = get_config_path();
     
@@ -61,6 +106,16 @@ pub fn save_config(config: &AppConfig) -> Result<(), String> {
     Ok(())
 }
 
+/// 保存新的登录信息，保留已保存的网络设置、请求头和离线模式
+pub fn save_login_config(config: &mut AppConfig) -> Result<(), String> {
+    if let Some(saved) = load_config()? {
+        config.network = saved.network;
+        config.headers = saved.headers;
+        config.offline = saved.offline;
+    }
+    save_config(config)
+}
+
 pub fn delete_config() -> Result<(), String> {
     let config_path = get_config_path();
     
@@ -70,4 +125,17 @@ pub fn delete_config() -> Result<(), String> {
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_proxy_credentials() {
+        let masked = |proxy: &str| NetworkConfig { proxy: Some(proxy.to_string()), ..Default::default() }.masked_proxy();
+        assert_eq!(masked("socks5://user:p@ss@127.0.0.1:1080"), Some("socks5://***@127.0.0.1:1080".to_string()));
+        assert_eq!(masked("http://token@proxy.local/path@x"), Some("http://***@proxy.local/path@x".to_string()));
+        assert_eq!(masked("http://127.0.0.1:7890"), Some("http://127.0.0.1:7890".to_string()));
+        assert_eq!(NetworkConfig::default().masked_proxy(), None);
+    }
+}
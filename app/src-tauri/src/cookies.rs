@@ -0,0 +1,438 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use chrono::Local;
+use crate::config::AppConfig;
+
+/// 未能从 Cookie 中解析出过期时间时默认的有效期
+const DEFAULT_COOKIE_LIFETIME: i64 = 30 * 24 * 60 * 60;
+
+const BILIBILI_DOMAIN: &str = ".bilibili.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CookieFormat {
+    /// 请求头格式：`name=value; name2=value2`
+    Header,
+    /// JSON 数组：`[{"name": "...", "value": "..."}]`
+    JsonArray,
+    /// Netscape `cookies.txt`
+    Netscape,
+    /// EditThisCookie 插件导出的 JSON
+    EditThisCookie,
+    /// 旧版 Node 后端使用的 TinyDB 格式
+    TinyDb,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    /// 过期时间戳（秒），为空表示会话 Cookie
+    pub expires: Option<i64>,
+    pub http_only: bool,
+    pub secure: bool,
+    pub same_site: Option<String>,
+}
+
+impl Cookie {
+    /// 只有名称和值的 B站 Cookie，其余属性按 B站 的习惯补全
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: BILIBILI_DOMAIN.to_string(),
+            path: "/".to_string(),
+            expires: expiry_from_value(value),
+            http_only: matches!(name, "SESSDATA" | "bili_jct"),
+            secure: true,
+            same_site: None,
+        }
+    }
+}
+
+/// SESSDATA 的值形如 `xxx%2C1700000000%2Cxxx`，第二段为过期时间戳
+pub fn expiry_from_value(value: &str) -> Option<i64> {
+    value.split("%2C").nth(1).and_then(|ts| ts.parse().ok())
+}
+
+/// 根据内容猜测 Cookie 格式
+pub fn detect_format(input: &str) -> CookieFormat {
+    let trimmed = input.trim_start();
+
+    if trimmed.starts_with('[') {
+        // EditThisCookie 导出的条目带有 storeId / hostOnly / expirationDate 等字段
+        if trimmed.contains("\"expirationDate\"") || trimmed.contains("\"hostOnly\"") || trimmed.contains("\"storeId\"") {
+            CookieFormat::EditThisCookie
+        } else {
+            CookieFormat::JsonArray
+        }
+    } else if trimmed.starts_with('{') {
+        CookieFormat::TinyDb
+    } else if trimmed.lines().any(|line| line.split('\t').count() >= 7) {
+        CookieFormat::Netscape
+    } else {
+        CookieFormat::Header
+    }
+}
+
+fn parse_header(input: &str) -> Vec<Cookie> {
+    input.split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .map(|(name, value)| Cookie::new(name, value))
+        .collect()
+}
+
+fn parse_netscape(input: &str) -> Result<Vec<Cookie>, String> {
+    let mut cookies = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        // curl 等工具用 #HttpOnly_ 前缀标记 HttpOnly，其余 # 开头的为注释
+        let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+            Some(rest) => (rest, true),
+            None => (line, false),
+        };
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 7 {
+            return Err(format!("cookies.txt 第 {} 行格式不正确", index + 1));
+        }
+        let expires: i64 = fields[4].trim().parse()
+            .map_err(|_| format!("cookies.txt 第 {} 行过期时间无效", index + 1))?;
+
+        cookies.push(Cookie {
+            name: fields[5].to_string(),
+            value: fields[6].to_string(),
+            domain: fields[0].to_string(),
+            path: fields[2].to_string(),
+            expires: if expires > 0 { Some(expires) } else { None },
+            http_only,
+            secure: fields[3].eq_ignore_ascii_case("TRUE"),
+            same_site: None,
+        });
+    }
+
+    Ok(cookies)
+}
+
+fn parse_json_item(item: &Value) -> Option<Cookie> {
+    let text = |key: &str| item.get(key).and_then(|v| v.as_str());
+    let flag = |key: &str| item.get(key).and_then(|v| v.as_bool());
+    let name = text("name")?;
+    let value = text("value")?;
+    let defaults = Cookie::new(name, value);
+
+    // EditThisCookie 使用浮点数的 expirationDate，会话 Cookie 带有 session: true
+    let expires = match flag("session") {
+        Some(true) => None,
+        _ => item.get("expires").or_else(|| item.get("expirationDate"))
+            .and_then(|v| v.as_f64())
+            .map(|v| v as i64)
+            .or(defaults.expires),
+    };
+
+    Some(Cookie {
+        domain: text("domain").map(str::to_string).unwrap_or(defaults.domain),
+        path: text("path").map(str::to_string).unwrap_or(defaults.path),
+        expires,
+        http_only: flag("httpOnly").unwrap_or(defaults.http_only),
+        secure: flag("secure").unwrap_or(defaults.secure),
+        same_site: text("sameSite").and_then(normalize_same_site),
+        ..defaults
+    })
+}
+
+/// EditThisCookie 的 sameSite 取值为 no_restriction / lax / strict / unspecified，unspecified 视为未设置
+fn normalize_same_site(value: &str) -> Option<String> {
+    match value.to_ascii_lowercase().as_str() {
+        "no_restriction" | "none" => Some("None".to_string()),
+        "lax" => Some("Lax".to_string()),
+        "strict" => Some("Strict".to_string()),
+        "unspecified" | "" => None,
+        _ => Some(value.to_string()),
+    }
+}
+
+fn parse_json(input: &str) -> Result<Vec<Cookie>, String> {
+    let value: Value = serde_json::from_str(input)
+        .map_err(|e| format!("解析Cookie JSON失败: {}", e))?;
+
+    // TinyDB 格式的数据位于 _default."1".value 中
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(_) => value.pointer("/_default/1/value").and_then(|v| v.as_array())
+            .ok_or_else(|| "无法识别的Cookie JSON格式".to_string())?,
+        _ => return Err("无法识别的Cookie JSON格式".to_string()),
+    };
+
+    Ok(items.iter().filter_map(parse_json_item).collect())
+}
+
+/// 解析 Cookie，未指定格式时自动识别
+pub fn parse_cookies(input: &str, format: Option<CookieFormat>) -> Result<Vec<Cookie>, String> {
+    let cookies = match format.unwrap_or_else(|| detect_format(input)) {
+        CookieFormat::Header => parse_header(input),
+        CookieFormat::Netscape => parse_netscape(input)?,
+        CookieFormat::JsonArray | CookieFormat::EditThisCookie | CookieFormat::TinyDb => parse_json(input)?,
+    };
+
+    if cookies.is_empty() {
+        return Err("未解析到任何Cookie".to_string());
+    }
+
+    Ok(cookies)
+}
+
+/// 转换为请求头使用的 `name=value; name2=value2`
+pub fn to_header(cookies: &[Cookie]) -> String {
+    cookies.iter()
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn to_netscape(cookies: &[Cookie]) -> String {
+    let mut lines = vec!["# Netscape HTTP Cookie File".to_string()];
+    for cookie in cookies {
+        lines.push(format!(
+            "{}{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            if cookie.http_only { "#HttpOnly_" } else { "" },
+            cookie.domain,
+            if cookie.domain.starts_with('.') { "TRUE" } else { "FALSE" },
+            cookie.path,
+            if cookie.secure { "TRUE" } else { "FALSE" },
+            cookie.expires.unwrap_or(0),
+            cookie.name,
+            cookie.value,
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// 旧版 Node 后端 `/api/convert-cookies` 生成的 TinyDB 格式
+pub fn to_tinydb(cookies: &[Cookie]) -> Value {
+    let default_expires = Local::now().timestamp() + DEFAULT_COOKIE_LIFETIME;
+    let value: Vec<Value> = cookies.iter().map(|c| json!({
+        "name": c.name,
+        "value": c.value,
+        "domain": c.domain,
+        "path": c.path,
+        "expires": c.expires.unwrap_or(default_expires),
+        "httpOnly": c.http_only,
+        "secure": c.secure,
+        "sameSite": c.same_site.as_deref().unwrap_or("Lax"),
+    })).collect();
+
+    json!({ "_default": { "1": { "value": value } } })
+}
+
+fn to_json_array(cookies: &[Cookie]) -> Value {
+    Value::Array(cookies.iter().map(|c| json!({
+        "name": c.name,
+        "value": c.value,
+        "domain": c.domain,
+        "path": c.path,
+        "expires": c.expires,
+        "httpOnly": c.http_only,
+        "secure": c.secure,
+        "sameSite": c.same_site,
+    })).collect())
+}
+
+fn to_edit_this_cookie(cookies: &[Cookie]) -> Value {
+    Value::Array(cookies.iter().enumerate().map(|(index, c)| {
+        let mut item = json!({
+            "domain": c.domain,
+            "hostOnly": !c.domain.starts_with('.'),
+            "httpOnly": c.http_only,
+            "name": c.name,
+            "path": c.path,
+            "sameSite": match c.same_site.as_deref() {
+                Some("None") => "no_restriction",
+                Some("Lax") => "lax",
+                Some("Strict") => "strict",
+                _ => "unspecified",
+            },
+            "secure": c.secure,
+            "session": c.expires.is_none(),
+            "storeId": "0",
+            "value": c.value,
+            "id": index + 1,
+        });
+        if let Some(expires) = c.expires {
+            item["expirationDate"] = json!(expires);
+        }
+        item
+    }).collect())
+}
+
+/// 将 Cookie 序列化为指定格式的文本
+pub fn serialize_cookies(cookies: &[Cookie], format: CookieFormat) -> Result<String, String> {
+    let value = match format {
+        CookieFormat::Header => return Ok(to_header(cookies)),
+        CookieFormat::Netscape => return Ok(to_netscape(cookies)),
+        CookieFormat::JsonArray => to_json_array(cookies),
+        CookieFormat::EditThisCookie => to_edit_this_cookie(cookies),
+        CookieFormat::TinyDb => to_tinydb(cookies),
+    };
+
+    serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("序列化Cookie失败: {}", e))
+}
+
+/// 根据 Cookie 生成配置，csrf_token 取自 bili_jct，up_mid 取自 DedeUserID
+///
+/// 只保留 B站 域名下的 Cookie，避免把浏览器导出的其他网站 Cookie 一并保存。
+pub fn config_from_cookies(cookies: &[Cookie]) -> Result<AppConfig, String> {
+    let cookies: Vec<Cookie> = cookies.iter()
+        .filter(|c| {
+            let domain = c.domain.trim_start_matches('.');
+            domain == "bilibili.com" || domain.ends_with(".bilibili.com")
+        })
+        .cloned()
+        .collect();
+    let find = |name: &str| cookies.iter().find(|c| c.name == name).map(|c| c.value.clone());
+
+    let missing: Vec<&str> = ["SESSDATA", "bili_jct", "DedeUserID"].into_iter()
+        .filter(|name| find(name).is_none())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Cookie缺少必要字段: {}", missing.join(", ")));
+    }
+
+    Ok(AppConfig {
+        up_mid: find("DedeUserID").unwrap_or_default(),
+        csrf_token: find("bili_jct").unwrap_or_default(),
+        cookie: to_header(&cookies),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSDATA: &str = "abc%2C1893456000%2Cdef";
+
+    fn login_cookies() -> Vec<Cookie> {
+        vec![
+            Cookie::new("SESSDATA", SESSDATA),
+            Cookie { expires: Some(1_893_456_000), ..Cookie::new("bili_jct", "csrf123") },
+            Cookie { expires: Some(1_893_456_000), same_site: Some("Lax".to_string()), ..Cookie::new("DedeUserID", "42") },
+            Cookie { expires: None, http_only: false, secure: false, ..Cookie::new("buvid3", "visitor") },
+        ]
+    }
+
+    #[test]
+    fn detects_formats() {
+        assert_eq!(detect_format("SESSDATA=a; bili_jct=b"), CookieFormat::Header);
+        assert_eq!(detect_format(r#"[{"name": "a", "value": "b"}]"#), CookieFormat::JsonArray);
+        assert_eq!(detect_format(r#"  [{"name": "a", "value": "b", "hostOnly": false, "storeId": "0"}]"#), CookieFormat::EditThisCookie);
+        assert_eq!(detect_format(r#"{"_default": {"1": {"value": []}}}"#), CookieFormat::TinyDb);
+        assert_eq!(detect_format("# Netscape HTTP Cookie File\n.bilibili.com\tTRUE\t/\tTRUE\t0\ta\tb\n"), CookieFormat::Netscape);
+        assert_eq!(detect_format("#HttpOnly_.bilibili.com\tTRUE\t/\tTRUE\t1893456000\tSESSDATA\tx\n"), CookieFormat::Netscape);
+    }
+
+    #[test]
+    fn parses_header_with_bilibili_defaults() {
+        let cookies = parse_cookies(&format!("SESSDATA={}; bili_jct=csrf123 ; empty=; DedeUserID=42", SESSDATA), None).unwrap();
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies[0].expires, Some(1_893_456_000));
+        assert!(cookies[0].http_only && cookies[1].http_only && !cookies[2].http_only);
+        assert!(cookies.iter().all(|c| c.domain == ".bilibili.com" && c.path == "/"));
+    }
+
+    #[test]
+    fn parses_netscape_http_only_lines() {
+        let input = "# Netscape HTTP Cookie File\n#HttpOnly_.bilibili.com\tTRUE\t/\tTRUE\t1893456000\tSESSDATA\tx\r\n# 注释\n\nwww.bilibili.com\tFALSE\t/video\tFALSE\t0\tbuvid3\ty\n";
+        let cookies = parse_cookies(input, None).unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert!(cookies[0].http_only && cookies[0].secure);
+        assert_eq!(cookies[0].expires, Some(1_893_456_000));
+        assert_eq!((cookies[1].domain.as_str(), cookies[1].path.as_str()), ("www.bilibili.com", "/video"));
+        assert!(!cookies[1].http_only && !cookies[1].secure && cookies[1].expires.is_none());
+
+        assert!(parse_cookies("a\tb\tc\td\tnot-a-number\tf\tg", Some(CookieFormat::Netscape)).is_err());
+    }
+
+    #[test]
+    fn parses_edit_this_cookie_export() {
+        let input = r#"[
+            {"domain": ".bilibili.com", "expirationDate": 1893456000.5, "hostOnly": false, "httpOnly": true, "name": "SESSDATA", "path": "/", "sameSite": "no_restriction", "secure": true, "session": false, "storeId": "0", "value": "x"},
+            {"domain": ".bilibili.com", "hostOnly": false, "httpOnly": false, "name": "buvid3", "path": "/", "sameSite": "unspecified", "secure": false, "session": true, "storeId": "0", "value": "y"}
+        ]"#;
+        let cookies = parse_cookies(input, None).unwrap();
+        assert_eq!(cookies[0].expires, Some(1_893_456_000));
+        assert_eq!(cookies[0].same_site.as_deref(), Some("None"));
+        assert_eq!(cookies[1].expires, None);
+        assert_eq!(cookies[1].same_site, None);
+    }
+
+    #[test]
+    fn round_trips_every_format() {
+        let cookies = login_cookies();
+        for format in [CookieFormat::JsonArray, CookieFormat::EditThisCookie, CookieFormat::TinyDb] {
+            let text = serialize_cookies(&cookies, format).unwrap();
+            assert_eq!(detect_format(&text), format);
+            let parsed = parse_cookies(&text, None).unwrap();
+            match format {
+                // TinyDB 为会话 Cookie 补全默认过期时间和 sameSite
+                CookieFormat::TinyDb => {
+                    assert_eq!(parsed[..3], cookies[..3].iter().cloned().map(|c| Cookie { same_site: Some("Lax".to_string()), ..c }).collect::<Vec<_>>()[..]);
+                    assert!(parsed[3].expires.is_some());
+                }
+                _ => assert_eq!(parsed, cookies),
+            }
+        }
+
+        // cookies.txt 不保存 sameSite
+        let without_same_site: Vec<Cookie> = cookies.iter().cloned().map(|c| Cookie { same_site: None, ..c }).collect();
+        let text = serialize_cookies(&without_same_site, CookieFormat::Netscape).unwrap();
+        assert_eq!(detect_format(&text), CookieFormat::Netscape);
+        assert_eq!(parse_cookies(&text, None).unwrap(), without_same_site);
+
+        // 请求头只保存名称和值
+        let text = serialize_cookies(&cookies, CookieFormat::Header).unwrap();
+        assert_eq!(text, format!("SESSDATA={}; bili_jct=csrf123; DedeUserID=42; buvid3=visitor", SESSDATA));
+        let parsed = parse_cookies(&text, None).unwrap();
+        assert_eq!(parsed.iter().map(|c| (c.name.as_str(), c.value.as_str())).collect::<Vec<_>>(),
+            cookies.iter().map(|c| (c.name.as_str(), c.value.as_str())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn builds_config_from_bilibili_cookies_only() {
+        let mut cookies = login_cookies();
+        cookies.push(Cookie { domain: ".example.com".to_string(), ..Cookie::new("other", "1") });
+        cookies.push(Cookie { domain: "notbilibili.com".to_string(), ..Cookie::new("fake", "1") });
+        cookies.push(Cookie { domain: "api.bilibili.com".to_string(), ..Cookie::new("sid", "2") });
+
+        let config = config_from_cookies(&cookies).unwrap();
+        assert_eq!(config.up_mid, "42");
+        assert_eq!(config.csrf_token, "csrf123");
+        assert_eq!(config.cookie, format!("SESSDATA={}; bili_jct=csrf123; DedeUserID=42; buvid3=visitor; sid=2", SESSDATA));
+    }
+
+    #[test]
+    fn rejects_cookies_without_login_fields() {
+        for missing in ["SESSDATA", "bili_jct", "DedeUserID"] {
+            let cookies: Vec<Cookie> = login_cookies().into_iter().filter(|c| c.name != missing).collect();
+            let error = config_from_cookies(&cookies).unwrap_err();
+            assert!(error.contains(missing), "{}", error);
+        }
+
+        // 其他网站的同名 Cookie 不算
+        let cookies = vec![
+            Cookie::new("SESSDATA", SESSDATA),
+            Cookie::new("bili_jct", "csrf123"),
+            Cookie { domain: ".example.com".to_string(), ..Cookie::new("DedeUserID", "42") },
+        ];
+        assert!(config_from_cookies(&cookies).unwrap_err().contains("DedeUserID"));
+    }
+}
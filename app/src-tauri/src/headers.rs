@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use reqwest::RequestBuilder;
+
+const CHROME_UA_WINDOWS: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36";
+const CHROME_UA_MACOS: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36";
+const CHROMIUM_ACCEPT_LANGUAGE: &str = "zh-CN,zh-HK;q=0.9,zh;q=0.8,en-US;q=0.7,en;q=0.6";
+
+/// 请求头配置，决定所有请求携带的浏览器特征
+///
+/// 为空的可选字段不会发送，例如 Firefox 和 Safari 不发送 Sec-Ch-Ua 系列请求头。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeaderProfile {
+    /// 预设名称，修改过的配置为 custom
+    pub name: String,
+    pub user_agent: String,
+    #[serde(default)]
+    pub accept_language: Option<String>,
+    #[serde(default)]
+    pub sec_ch_ua: Option<String>,
+    #[serde(default)]
+    pub sec_ch_ua_mobile: Option<String>,
+    #[serde(default)]
+    pub sec_ch_ua_platform: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+}
+
+impl Default for HeaderProfile {
+    fn default() -> Self {
+        Self::chromium("chrome_windows", CHROME_UA_WINDOWS, "Google Chrome", "Windows")
+    }
+}
+
+impl HeaderProfile {
+    fn chromium(name: &str, user_agent: &str, brand: &str, platform: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            user_agent: user_agent.to_string(),
+            accept_language: Some(CHROMIUM_ACCEPT_LANGUAGE.to_string()),
+            sec_ch_ua: Some(format!("\"{}\";v=\"141\", \"Not?A_Brand\";v=\"8\", \"Chromium\";v=\"141\"", brand)),
+            sec_ch_ua_mobile: Some("?0".to_string()),
+            sec_ch_ua_platform: Some(format!("\"{}\"", platform)),
+            priority: Some("u=1, i".to_string()),
+        }
+    }
+
+    fn without_client_hints(name: &str, user_agent: &str, accept_language: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            user_agent: user_agent.to_string(),
+            accept_language: Some(accept_language.to_string()),
+            sec_ch_ua: None,
+            sec_ch_ua_mobile: None,
+            sec_ch_ua_platform: None,
+            priority: None,
+        }
+    }
+
+    /// 内置的浏览器预设
+    pub fn presets() -> Vec<HeaderProfile> {
+        vec![
+            Self::default(),
+            Self::chromium("chrome_macos", CHROME_UA_MACOS, "Google Chrome", "macOS"),
+            Self::chromium(
+                "edge_windows",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36 Edg/141.0.0.0",
+                "Microsoft Edge",
+                "Windows",
+            ),
+            Self::without_client_hints(
+                "firefox_windows",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:143.0) Gecko/20100101 Firefox/143.0",
+                "zh-CN,zh;q=0.8,zh-TW;q=0.7,zh-HK;q=0.5,en-US;q=0.3,en;q=0.2",
+            ),
+            Self::without_client_hints(
+                "safari_macos",
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.6 Safari/605.1.15",
+                "zh-CN,zh-Hans;q=0.9",
+            ),
+        ]
+    }
+
+    pub fn preset(name: &str) -> Option<HeaderProfile> {
+        Self::presets().into_iter().find(|p| p.name == name)
+    }
+
+    /// 为请求添加浏览器特征和来源页面对应的 Referer / Origin
+    pub fn apply(&self, request: RequestBuilder, page: &Page) -> RequestBuilder {
+        let optional = [
+            ("Accept-Language", &self.accept_language),
+            ("Sec-Ch-Ua", &self.sec_ch_ua),
+            ("Sec-Ch-Ua-Mobile", &self.sec_ch_ua_mobile),
+            ("Sec-Ch-Ua-Platform", &self.sec_ch_ua_platform),
+            ("Priority", &self.priority),
+        ];
+
+        let request = optional.into_iter()
+            .filter_map(|(name, value)| value.as_deref().filter(|v| !v.is_empty()).map(|v| (name, v)))
+            .fold(request, |request, (name, value)| request.header(name, value));
+
+        request
+            .header("User-Agent", &self.user_agent)
+            .header("Accept", "application/json, text/plain, */*")
+            .header("Sec-Fetch-Dest", "empty")
+            .header("Sec-Fetch-Mode", "cors")
+            .header("Sec-Fetch-Site", "same-site")
+            .header("Referer", page.referer())
+            .header("Origin", page.origin())
+    }
+}
+
+/// 发起请求的页面，对应浏览器中该操作所在的网页
+#[derive(Debug, Clone)]
+pub enum Page {
+    /// 主站页面
+    Home,
+    /// 个人空间的收藏夹页面
+    Favlist { up_mid: String, media_id: Option<i64> },
+    /// 稍后再看页面
+    WatchLater,
+}
+
+impl Page {
+    pub fn favlist(up_mid: &str) -> Self {
+        Page::Favlist { up_mid: up_mid.to_string(), media_id: None }
+    }
+
+    pub fn favlist_folder(up_mid: &str, media_id: i64) -> Self {
+        Page::Favlist { up_mid: up_mid.to_string(), media_id: Some(media_id) }
+    }
+
+    pub fn referer(&self) -> String {
+        match self {
+            Page::Home => "https://www.bilibili.com/".to_string(),
+            Page::Favlist { up_mid, media_id: None } => format!("https://space.bilibili.com/{}/favlist", up_mid),
+            Page::Favlist { up_mid, media_id: Some(media_id) } => format!("https://space.bilibili.com/{}/favlist?fid={}", up_mid, media_id),
+            Page::WatchLater => "https://www.bilibili.com/watchlater/".to_string(),
+        }
+    }
+
+    pub fn origin(&self) -> &'static str {
+        match self {
+            Page::Favlist { .. } => "https://space.bilibili.com",
+            Page::Home | Page::WatchLater => "https://www.bilibili.com",
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! 检测新失效的视频：将收藏夹的最新内容与本地索引中已知的状态比较，失效前的标题从本地数据中找回
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use chrono::Local;
+use crate::bilibili::{BilibiliClient, FavoriteResource, ResourceId, ResourceType};
+use crate::cache::FavoritesCache;
+use crate::config::{get_config_dir, AppConfig};
+use crate::FolderFailure;
+
+/// 历史记录最多保留的条数
+const MAX_HISTORY_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidatedVideo {
+    pub folder_id: i64,
+    pub folder_title: String,
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub resource_type: ResourceType,
+    pub bvid: String,
+    /// 失效前最后已知的标题
+    pub title: String,
+    pub upper: Option<String>,
+    pub detected_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvalidationCheck {
+    pub videos: Vec<InvalidatedVideo>,
+    /// 获取内容失败的收藏夹，这些收藏夹保留原有基准，下次检查时重新比较
+    pub failures: Vec<FolderFailure>,
+}
+
+/// 获取全部收藏夹的最新内容，返回上次已知时正常、现在已失效的视频
+///
+/// 本地没有索引记录的收藏夹只建立基准，不产生提醒。检查结果写入历史记录后，获取到的内容才会写入缓存和索引，
+/// 作为下一次比较的基准，避免中途出错时新失效的视频被计入基准而不再提醒。
+pub async fn detect_invalidated(client: &BilibiliClient, config: &AppConfig) -> Result<InvalidationCheck, String> {
+    let cache = FavoritesCache::new(&config.up_mid);
+    let folders = client.get_favorite_folders(config).await?;
+    cache.save_folders(&folders)?;
+
+    let mut fetched = Vec::new();
+    let mut failures = Vec::new();
+    for folder in &folders {
+        match client.get_favorite_videos(folder.id, folder.media_count, config).await {
+            Ok(resources) => fetched.push((folder, resources)),
+            Err(error) => failures.push(FolderFailure { folder_id: folder.id, error }),
+        }
+    }
+    if fetched.is_empty() && !failures.is_empty() {
+        return Err(failures.remove(0).error);
+    }
+
+    let index = cache.index();
+    let mut detected = Vec::new();
+    for (folder, resources) in &fetched {
+        let known: HashMap<ResourceId, &FavoriteResource> = index.folder_resources(folder.id).into_iter()
+            .map(|r| (ResourceId::from(r), r))
+            .collect();
+
+        for resource in resources.iter().filter(|r| r.is_invalid()) {
+            let Some(previous) = known.get(&ResourceId::from(resource)).filter(|r| !r.is_invalid()) else {
+                continue;
+            };
+
+            detected.push(InvalidatedVideo {
+                folder_id: folder.id,
+                folder_title: folder.title.clone(),
+                id: resource.id,
+                resource_type: resource.resource_type,
+                bvid: resource.bvid.clone(),
+                title: previous.title.clone(),
+                upper: previous.upper.as_ref().map(|u| u.name.clone()),
+                detected_at: Local::now().timestamp(),
+            });
+        }
+    }
+
+    if !detected.is_empty() {
+        append_history(&config.up_mid, &detected)?;
+    }
+    for (folder, resources) in &fetched {
+        cache.save_resources(folder, resources, true)?;
+    }
+    Ok(InvalidationCheck { videos: detected, failures })
+}
+
+fn history_path(up_mid: &str) -> PathBuf {
+    get_config_dir().join("invalidated").join(format!("{}.json", up_mid))
+}
+
+/// 已检测到的失效视频，从旧到新排列
+pub fn load_history(up_mid: &str) -> Result<Vec<InvalidatedVideo>, String> {
+    let path = history_path(up_mid);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("读取失效记录失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析失效记录失败: {}", e))
+}
+
+fn append_history(up_mid: &str, videos: &[InvalidatedVideo]) -> Result<(), String> {
+    let mut history = load_history(up_mid).unwrap_or_default();
+    history.extend_from_slice(videos);
+    if history.len() > MAX_HISTORY_ENTRIES {
+        history.drain(..history.len() - MAX_HISTORY_ENTRIES);
+    }
+
+    let path = history_path(up_mid);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&history)
+        .map_err(|e| format!("序列化失效记录失败: {}", e))?;
+    fs::write(&path, content)
+        .map_err(|e| format!("写入失效记录失败: {}", e))
+}
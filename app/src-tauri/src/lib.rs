@@ -1,9 +1,40 @@
-mod config;
-mod bilibili;
+pub mod config;
+pub mod headers;
+pub mod cookies;
+pub mod browser;
+pub mod cache;
+pub mod search;
+pub mod availability;
+pub mod snapshot;
+pub mod invalidation;
+pub mod organize;
+pub mod privacy;
+pub mod bilibili;
+pub mod stats;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "desktop")]
+mod schedule;
 
-use config::{AppConfig, load_config, save_config, delete_config};
-use bilibili::{BilibiliClient, FavoriteFolder, FavoriteDetailData, FavoriteResource, MoveResult, MoveVideoInfo};
+use config::{AppConfig, load_config, save_config, save_login_config, delete_config};
+use bilibili::{BilibiliClient, CollectedFolder, WatchLaterItem, ResourceType, FavoriteFolder, FavoriteDetailData, FavoriteResource, MoveResult, MoveVideoInfo, MoveDirection, MoveFilter, ResourceId, FIRST_PAGE_SIZE, MAX_FAVORITES_LIMIT};
+use cookies::{Cookie, CookieFormat};
+use headers::HeaderProfile;
+use browser::BrowserProfile;
+use cache::FavoritesCache;
+use search::SearchHit;
+use availability::{Availability, AvailabilityReport};
+use snapshot::{Snapshot, SnapshotDiff, SnapshotSummary};
+use invalidation::{InvalidatedVideo, InvalidationCheck};
+use organize::{MergeReport, RenameItem, RenameResult, RenameRule, SplitCriterion, SplitPreview, SplitReport};
+use privacy::{PrivacyAudit, PrivacyChangeResult};
+use stats::FavoritesStats;
+#[cfg(feature = "desktop")]
+use schedule::{ScheduleConfig, ScheduleLogEntry};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "desktop")]
+use tauri::{AppHandle, Emitter};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CleanResult {
@@ -19,7 +50,7 @@ pub struct BatchCleanResult {
     pub summary: CleanSummary,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CleanSummary {
     pub total: usize,
     pub success: usize,
@@ -27,6 +58,43 @@ pub struct CleanSummary {
     pub total_cleaned: i32,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsubscribeResult {
+    pub id: i64,
+    pub title: String,
+    pub is_season: bool,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchUnsubscribeResult {
+    pub results: Vec<UnsubscribeResult>,
+    pub success: usize,
+    pub failure: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchLaterFailure {
+    pub aid: i64,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchLaterMoveResult {
+    pub target_id: i64,
+    pub moved_count: i32,
+    pub skipped_count: i32,
+    pub message: String,
+    pub failures: Vec<WatchLaterFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchLaterCleanResult {
+    pub removed_count: i32,
+    pub failures: Vec<WatchLaterFailure>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SortResult {
     pub success: bool,
@@ -35,6 +103,21 @@ pub struct SortResult {
     pub folder_ids: Vec<i64>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResourceSortResult {
+    pub media_id: i64,
+    pub success: bool,
+    pub message: String,
+    pub sorted_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSortProgress {
+    pub media_id: i64,
+    pub processed: usize,
+    pub total: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MoveExecuteResult {
     pub src_media_id: i64,
@@ -44,63 +127,558 @@ pub struct MoveExecuteResult {
     pub videos: Vec<MoveVideoInfo>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResourceBatchResult {
+    pub success: bool,
+    pub count: i32,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderFailure {
+    pub folder_id: i64,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AvailabilityCleanResult {
+    pub removed_count: i32,
+    pub failures: Vec<FolderFailure>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MoveCalculateResult {
     pub src_folder: FavoriteFolder,
     pub tar_folder: FavoriteFolder,
+    pub matched_count: i32,
     pub movable_count: i32,
     pub can_move: bool,
+    pub videos: Vec<FavoriteResource>,
+}
+
+/// B站收藏夹内搜索的结果，标明所在的收藏夹
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderSearchHit {
+    pub folder_id: i64,
+    pub folder_title: String,
+    pub resource: FavoriteResource,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub folder_count: usize,
+    /// 重新获取了内容的收藏夹数量
+    pub refreshed_count: usize,
+    pub resource_count: usize,
+}
+
+/// 登录账号的基本信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub mid: i64,
+    pub name: String,
+    pub face: String,
+    pub level: i32,
+    pub is_vip: bool,
+    pub vip_type: i32,
+    pub vip_label: Option<String>,
 }
 
 // 配置相关命令
-#[tauri::command]
-async fn get_config() -> Result<Option<AppConfig>, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_config() -> Result<Option<AppConfig>, String> {
     load_config()
 }
 
-#[tauri::command]
-async fn save_config_command(config: AppConfig) -> Result<(), String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn save_config_command(config: AppConfig) -> Result<(), String> {
+    // 先按网络设置创建一次客户端，代理地址或证书有误时不保存
+    if !config.offline {
+        BilibiliClient::from_config(&config)?;
+    }
     save_config(&config)
 }
 
-#[tauri::command]
-async fn delete_config_command() -> Result<(), String> {
+/// 内置的浏览器请求头预设
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_header_presets() -> Result<Vec<HeaderProfile>, String> {
+    Ok(HeaderProfile::presets())
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn delete_config_command() -> Result<(), String> {
     delete_config()
 }
 
-// 收藏夹相关命令
+/// 校验配置中的 Cookie 是否已登录，并且与 up_mid、csrf_token 属于同一账号
+///
+/// 未传入配置时校验已保存的配置。
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn verify_account(config: Option<AppConfig>) -> Result<AccountInfo, String> {
+    let config = match config {
+        Some(config) => config,
+        None => load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?,
+    };
+
+    let cookies = cookies::parse_cookies(&config.cookie, Some(CookieFormat::Header))?;
+    let find = |name: &str| cookies.iter().find(|c| c.name == name).map(|c| c.value.as_str());
+
+    match find("DedeUserID") {
+        Some(mid) if mid == config.up_mid => {}
+        Some(mid) => return Err(format!("up_mid（{}）与Cookie中的DedeUserID（{}）不一致", config.up_mid, mid)),
+        None => return Err("Cookie中缺少DedeUserID".to_string()),
+    }
+    match find("bili_jct") {
+        Some(jct) if jct == config.csrf_token => {}
+        Some(_) => return Err("csrf_token与Cookie中的bili_jct不一致".to_string()),
+        None => return Err("Cookie中缺少bili_jct".to_string()),
+    }
+
+    let nav = BilibiliClient::from_config(&config)?.get_nav_info(&config).await?;
+    if !nav.is_login {
+        return Err("Cookie已失效，请重新登录".to_string());
+    }
+    if nav.mid.to_string() != config.up_mid {
+        return Err(format!("Cookie登录的账号（{}）与up_mid（{}）不一致", nav.mid, config.up_mid));
+    }
+
+    Ok(AccountInfo {
+        mid: nav.mid,
+        name: nav.uname,
+        face: nav.face,
+        level: nav.level_info.map(|l| l.current_level).unwrap_or_default(),
+        is_vip: nav.vip_status == 1,
+        vip_type: nav.vip_type,
+        vip_label: nav.vip_label.map(|l| l.text).filter(|t| !t.is_empty()),
+    })
+}
+
+// Cookie相关命令
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn parse_cookies(input: String, format: Option<CookieFormat>) -> Result<Vec<Cookie>, String> {
+    cookies::parse_cookies(&input, format)
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn convert_cookies(input: String, from: Option<CookieFormat>, to: CookieFormat) -> Result<String, String> {
+    let cookies = cookies::parse_cookies(&input, from)?;
+    cookies::serialize_cookies(&cookies, to)
+}
+
+/// 解析 Cookie 并保存为配置，返回保存后的配置
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn import_cookies(input: String, format: Option<CookieFormat>) -> Result<AppConfig, String> {
+    let cookies = cookies::parse_cookies(&input, format)?;
+    let mut config = cookies::config_from_cookies(&cookies)?;
+    save_login_config(&mut config)?;
+    Ok(config)
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_browser_profiles() -> Result<Vec<BrowserProfile>, String> {
+    Ok(browser::find_profiles())
+}
+
+/// 从本机浏览器读取登录 Cookie 并保存为配置，未指定 Cookie 数据库时依次尝试所有配置文件
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn import_browser_cookies(cookie_path: Option<String>) -> Result<AppConfig, String> {
+    let profiles: Vec<BrowserProfile> = browser::find_profiles().into_iter()
+        .filter(|p| cookie_path.as_ref().is_none_or(|path| p.cookie_path == std::path::Path::new(path)))
+        .collect();
+    let (_, mut config) = browser::import_config(&profiles)?;
+    save_login_config(&mut config)?;
+    Ok(config)
+}
+
+// 定时清理相关命令
+#[cfg(feature = "desktop")]
 #[tauri::command]
-async fn get_favorites() -> Result<Vec<FavoriteFolder>, String> {
+async fn get_schedule_config() -> Result<ScheduleConfig, String> {
+    schedule::load_schedule()
+}
+
+#[cfg(feature = "desktop")]
+#[tauri::command]
+async fn save_schedule_config(mut config: ScheduleConfig) -> Result<(), String> {
+    config.validate()?;
+    // 新启用的计划从现在开始计时，不补执行之前的时间点
+    if config.enabled && config.last_run.is_none() {
+        config.last_run = Some(chrono::Local::now().timestamp());
+    }
+    schedule::save_schedule(&config)
+}
+
+#[cfg(feature = "desktop")]
+#[tauri::command]
+async fn get_schedule_log() -> Result<Vec<ScheduleLogEntry>, String> {
+    schedule::load_schedule_log()
+}
+
+// 收藏夹相关命令
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_favorites() -> Result<Vec<FavoriteFolder>, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => cached_folders(&config, false).await,
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_favorite_details(favorite_id: i64) -> Result<FavoriteDetailData, String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
-            client.get_favorite_folders(&config).await
+            let cache = FavoritesCache::new(&config.up_mid);
+            if let Some(cached) = cache.resources(favorite_id) {
+                if config.offline || cache::is_fresh(cached.fetched_at) {
+                    return Ok(FavoriteDetailData {
+                        has_more: !cached.complete || cached.resources.len() > FIRST_PAGE_SIZE,
+                        medias: Some(cached.resources.into_iter().take(FIRST_PAGE_SIZE).collect()),
+                        info: cached.folder,
+                    });
+                }
+            } else if config.offline {
+                return Err("离线模式下没有该收藏夹的缓存".to_string());
+            }
+
+            let client = BilibiliClient::from_config(&config)?;
+            let details = client.get_favorite_resources(favorite_id, &config).await?;
+            let _ = cache.save_resources(&details.info, details.medias.as_deref().unwrap_or_default(), !details.has_more);
+            Ok(details)
         }
         None => Err("请先配置Cookie".to_string())
     }
 }
 
-#[tauri::command]
-async fn get_favorite_details(favorite_id: i64) -> Result<FavoriteDetailData, String> {
+/// 获取收藏夹内的全部内容，`refresh` 为 true 时忽略缓存
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_folder_resources(media_id: i64, refresh: bool) -> Result<Vec<FavoriteResource>, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let cache = FavoritesCache::new(&config.up_mid);
+            match cache.resources(media_id) {
+                Some(cached) if config.offline => return Ok(cached.resources),
+                Some(cached) if !refresh && cached.complete && cache::is_fresh(cached.fetched_at) => return Ok(cached.resources),
+                None if config.offline => return Err("离线模式下没有该收藏夹的缓存".to_string()),
+                _ => {}
+            }
+
+            let client = BilibiliClient::from_config(&config)?;
+            let info = client.get_favorite_resources(media_id, &config).await?.info;
+            let resources = client.get_favorite_videos(media_id, info.media_count, &config).await?;
+            let _ = cache.save_resources(&info, &resources, true);
+            Ok(resources)
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+/// 扫描全部收藏夹并更新本地缓存，只重新获取有变化或缓存过期的收藏夹
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn scan_favorites() -> Result<ScanResult, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let folders = cached_folders(&config, true).await?;
+    let client = BilibiliClient::from_config(&config)?;
+    let cache = FavoritesCache::new(&config.up_mid);
+
+    let mut refreshed_count = 0;
+    let mut resource_count = 0;
+    for folder in &folders {
+        let cached = cache.resources(folder.id)
+            .filter(|c| c.complete && cache::is_fresh(c.fetched_at));
+        let resources = match cached {
+            Some(cached) => cached.resources,
+            None => {
+                let resources = client.get_favorite_videos(folder.id, folder.media_count, &config).await?;
+                cache.save_resources(folder, &resources, true)?;
+                refreshed_count += 1;
+                resources
+            }
+        };
+        resource_count += resources.len();
+    }
+
+    Ok(ScanResult {
+        folder_count: folders.len(),
+        refreshed_count,
+        resource_count,
+    })
+}
+
+/// 在本地索引中搜索全部收藏夹的标题、简介和UP主名称，可在离线模式下使用
+///
+/// 索引随扫描和修改操作更新，未扫描过的收藏夹不会出现在结果中。
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn search_favorites(keyword: String, limit: Option<usize>) -> Result<Vec<SearchHit>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let index = FavoritesCache::new(&config.up_mid).index();
+    Ok(index.search(&keyword, limit.unwrap_or(search::DEFAULT_LIMIT)))
+}
+
+/// 使用B站的收藏夹内搜索，`media_id` 为空时依次搜索全部收藏夹
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn search_favorites_online(keyword: String, media_id: Option<i64>) -> Result<Vec<FolderSearchHit>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return Err("请输入搜索关键词".to_string());
+    }
+
+    let client = BilibiliClient::from_config(&config)?;
+    let folders = client.get_favorite_folders(&config).await?;
+    let folders: Vec<FavoriteFolder> = match media_id {
+        Some(media_id) => {
+            let folder = folders.into_iter().find(|f| f.id == media_id)
+                .ok_or_else(|| "收藏夹不存在".to_string())?;
+            vec![folder]
+        }
+        None => folders.into_iter().filter(|f| f.media_count > 0).collect(),
+    };
+
+    let mut hits = Vec::new();
+    for folder in folders {
+        let resources = client.search_favorite_resources(folder.id, keyword, &config).await
+            .map_err(|e| format!("搜索收藏夹「{}」失败: {}", folder.title, e))?;
+        hits.extend(resources.into_iter().map(|resource| FolderSearchHit {
+            folder_id: folder.id,
+            folder_title: folder.title.clone(),
+            resource,
+        }));
+    }
+
+    Ok(hits)
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn clear_favorites_cache() -> Result<(), String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    FavoritesCache::new(&config.up_mid).clear()
+}
+
+/// 读取收藏夹列表，缓存未过期（或处于离线模式）时直接使用缓存
+async fn cached_folders(config: &AppConfig, refresh: bool) -> Result<Vec<FavoriteFolder>, String> {
+    let cache = FavoritesCache::new(&config.up_mid);
+    match cache.folders() {
+        Some(cached) if config.offline => return Ok(cached.folders),
+        Some(cached) if !refresh && cache::is_fresh(cached.fetched_at) => return Ok(cached.folders),
+        None if config.offline => return Err("离线模式下没有缓存的收藏夹".to_string()),
+        _ => {}
+    }
+
+    let client = BilibiliClient::from_config(config)?;
+    let folders = client.get_favorite_folders(config).await?;
+    cache.save_folders(&folders)?;
+    Ok(folders)
+}
+
+/// 读取收藏夹的全部内容，缓存完整且未过期时直接使用缓存
+async fn cached_resources(config: &AppConfig, client: &BilibiliClient, folder: &FavoriteFolder) -> Result<Vec<FavoriteResource>, String> {
+    let cache = FavoritesCache::new(&config.up_mid);
+    if let Some(cached) = cache.resources(folder.id).filter(|c| c.complete && cache::is_fresh(c.fetched_at)) {
+        return Ok(cached.resources);
+    }
+
+    let resources = client.get_favorite_videos(folder.id, folder.media_count, config).await?;
+    cache.save_resources(folder, &resources, true)?;
+    Ok(resources)
+}
+
+// 收藏和订阅相关命令
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_collected_folders() -> Result<Vec<CollectedFolder>, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            client.get_collected_folders(&config).await
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+/// 批量取消收藏/订阅；`all` 为 true 时处理全部项目并忽略 `ids`，`only_unavailable` 为 true 时只处理已失效或被设为私密的项目
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn unsubscribe_collected(ids: Vec<i64>, all: bool, only_unavailable: bool) -> Result<BatchUnsubscribeResult, String> {
+    if !all && ids.is_empty() {
+        return Err("请选择要取消的收藏或订阅".to_string());
+    }
+
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let collected = client.get_collected_folders(&config).await?;
+            let targets: Vec<_> = collected.iter()
+                .filter(|f| all || ids.contains(&f.id))
+                .filter(|f| !only_unavailable || f.is_unavailable())
+                .collect();
+
+            let mut results = Vec::new();
+            for folder in targets {
+                let outcome = client.unsubscribe_collected(folder, &config).await;
+                results.push(UnsubscribeResult {
+                    id: folder.id,
+                    title: folder.title.clone(),
+                    is_season: folder.is_season(),
+                    success: outcome.is_ok(),
+                    error: outcome.err(),
+                });
+            }
+
+            let success = results.iter().filter(|r| r.success).count();
+            let failure = results.len() - success;
+            Ok(BatchUnsubscribeResult { results, success, failure })
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+// 稍后再看相关命令
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_watch_later() -> Result<Vec<WatchLaterItem>, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            client.get_watch_later(&config).await
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn add_to_watch_later(bvid: String) -> Result<(), String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
-            client.get_favorite_resources(favorite_id, &config).await
+            let client = BilibiliClient::from_config(&config)?;
+            client.add_to_watch_later(&bvid, &config).await
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn delete_from_watch_later(aids: Vec<i64>) -> Result<WatchLaterCleanResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let mut removed_count = 0;
+            let mut failures = Vec::new();
+            for aid in aids {
+                match client.delete_from_watch_later(aid, &config).await {
+                    Ok(_) => removed_count += 1,
+                    Err(error) => failures.push(WatchLaterFailure { aid, error }),
+                }
+            }
+            Ok(WatchLaterCleanResult { removed_count, failures })
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+/// 将稍后再看中的视频移入收藏夹，超出目标收藏夹剩余容量的部分跳过
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn move_watch_later_to_folder(aids: Vec<i64>, target_id: i64) -> Result<WatchLaterMoveResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+
+            let tar_info = client.get_favorite_resources(target_id, &config).await?;
+            let available_space = tar_info.info.available_space() as usize;
+
+            if available_space == 0 {
+                return Ok(WatchLaterMoveResult {
+                    target_id,
+                    moved_count: 0,
+                    skipped_count: aids.len() as i32,
+                    message: format!("目标收藏夹已满（{}个视频上限）", MAX_FAVORITES_LIMIT),
+                    failures: vec![],
+                });
+            }
+
+            let selected: Vec<i64> = aids.iter().take(available_space).cloned().collect();
+            let skipped_count = (aids.len() - selected.len()) as i32;
+            let mut moved_count = 0;
+            let mut failures = Vec::new();
+
+            for aid in selected {
+                let resource = ResourceId { id: aid, resource_type: ResourceType::Video };
+                let outcome = match client.add_to_folders(resource, &[target_id], &config).await {
+                    Ok(_) => client.delete_from_watch_later(aid, &config).await,
+                    Err(error) => Err(error),
+                };
+                match outcome {
+                    Ok(_) => moved_count += 1,
+                    Err(error) => failures.push(WatchLaterFailure { aid, error }),
+                }
+            }
+
+            FavoritesCache::new(&config.up_mid).invalidate(&[target_id]);
+
+            Ok(WatchLaterMoveResult {
+                target_id,
+                moved_count,
+                skipped_count,
+                message: format!("成功移动 {} 个视频", moved_count),
+                failures,
+            })
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+/// 清理稍后再看中已看完和/或已失效的视频
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn clean_watch_later(watched: bool, invalid: bool) -> Result<WatchLaterCleanResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let items = client.get_watch_later(&config).await?;
+            let mut removed_count = 0;
+            let mut failures = Vec::new();
+
+            if watched {
+                let watched_count = items.iter().filter(|item| item.is_watched()).count() as i32;
+                client.clear_watched_watch_later(&config).await?;
+                removed_count += watched_count;
+            }
+
+            if invalid {
+                // 已看完的失效视频在上一步已被删除
+                for item in items.iter().filter(|item| item.is_invalid() && !(watched && item.is_watched())) {
+                    match client.delete_from_watch_later(item.aid, &config).await {
+                        Ok(_) => removed_count += 1,
+                        Err(error) => failures.push(WatchLaterFailure { aid: item.aid, error }),
+                    }
+                }
+            }
+
+            Ok(WatchLaterCleanResult { removed_count, failures })
         }
         None => Err("请先配置Cookie".to_string())
     }
 }
 
 // 清理相关命令
-#[tauri::command]
-async fn clean_favorite(media_id: i64) -> Result<CleanResult, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn clean_favorite(media_id: i64) -> Result<CleanResult, String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
-            match client.clean_favorite_folder(media_id, &config).await {
+            let client = BilibiliClient::from_config(&config)?;
+            let outcome = client.clean_favorite_folder(media_id, &config).await;
+            let cache = FavoritesCache::new(&config.up_mid);
+            cache.invalidate(&[media_id]);
+            if outcome.is_ok() {
+                let _ = cache.update_index(|index| index.remove_invalid(media_id));
+            }
+            match outcome {
                 Ok(cleaned_count) => Ok(CleanResult {
                     media_id,
                     success: true,
@@ -119,68 +697,217 @@ async fn clean_favorite(media_id: i64) -> Result<CleanResult, String> {
     }
 }
 
-#[tauri::command]
-async fn clean_multiple_favorites(media_ids: Vec<i64>) -> Result<BatchCleanResult, String> {
+/// 依次清理多个收藏夹中的失效内容并汇总结果
+pub async fn clean_folders(client: &BilibiliClient, config: &AppConfig, media_ids: Vec<i64>) -> BatchCleanResult {
+    let mut results = Vec::new();
+    let mut total_cleaned = 0;
+    let mut success_count = 0;
+
+    for media_id in media_ids {
+        match client.clean_favorite_folder(media_id, config).await {
+            Ok(cleaned_count) => {
+                total_cleaned += cleaned_count;
+                success_count += 1;
+                results.push(CleanResult {
+                    media_id,
+                    success: true,
+                    cleaned_count,
+                    error: None,
+                });
+            }
+            Err(error) => {
+                results.push(CleanResult {
+                    media_id,
+                    success: false,
+                    cleaned_count: 0,
+                    error: Some(error),
+                });
+            }
+        }
+    }
+
+    let media_ids: Vec<i64> = results.iter().map(|r| r.media_id).collect();
+    let cache = FavoritesCache::new(&config.up_mid);
+    cache.invalidate(&media_ids);
+    let _ = cache.update_index(|index| {
+        for result in results.iter().filter(|r| r.success) {
+            index.remove_invalid(result.media_id);
+        }
+    });
+
+    let total = results.len();
+    let failure_count = total - success_count;
+
+    BatchCleanResult {
+        results,
+        summary: CleanSummary {
+            total,
+            success: success_count,
+            failure: failure_count,
+            total_cleaned,
+        },
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn clean_multiple_favorites(media_ids: Vec<i64>) -> Result<BatchCleanResult, String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
-            let mut results = Vec::new();
-            let mut total_cleaned = 0;
-            let mut success_count = 0;
-            
-            for media_id in media_ids {
-                match client.clean_favorite_folder(media_id, &config).await {
-                    Ok(cleaned_count) => {
-                        total_cleaned += cleaned_count;
-                        success_count += 1;
-                        results.push(CleanResult {
-                            media_id,
-                            success: true,
-                            cleaned_count,
-                            error: None,
-                        });
-                    }
-                    Err(error) => {
-                        results.push(CleanResult {
-                            media_id,
-                            success: false,
-                            cleaned_count: 0,
-                            error: Some(error),
-                        });
-                    }
-                }
-            }
-            
-            let total = results.len();
-            let failure_count = total - success_count;
-            
-            Ok(BatchCleanResult {
-                results,
-                summary: CleanSummary {
-                    total,
-                    success: success_count,
-                    failure: failure_count,
-                    total_cleaned,
-                },
-            })
+            let client = BilibiliClient::from_config(&config)?;
+            Ok(clean_folders(&client, &config, media_ids).await)
         }
         None => Err("请先配置Cookie".to_string())
     }
 }
 
+// 可用性检查相关命令
+/// 逐个检查收藏夹中的视频能否观看，`media_ids` 为空时检查全部收藏夹，结果与之前的检查结果合并保存
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn check_availability(media_ids: Vec<i64>) -> Result<AvailabilityReport, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    let folders: Vec<FavoriteFolder> = cached_folders(&config, true).await?.into_iter()
+        .filter(|f| media_ids.is_empty() || media_ids.contains(&f.id))
+        .collect();
+    if folders.is_empty() {
+        return Err("收藏夹不存在".to_string());
+    }
+
+    let mut resources = Vec::new();
+    for folder in &folders {
+        let folder_resources = cached_resources(&config, &client, folder).await?;
+        resources.extend(folder_resources.into_iter().map(|r| (folder.id, r)));
+    }
+
+    let entries = availability::check_resources(&client, &config, resources).await;
+    let folder_ids: Vec<i64> = folders.iter().map(|f| f.id).collect();
+    let mut report = availability::load_report(&config.up_mid)?;
+    report.merge(&folder_ids, entries);
+    availability::save_report(&config.up_mid, &report)?;
+    Ok(report)
+}
+
+/// 立即检查是否有新失效的视频
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn detect_invalidated_videos() -> Result<InvalidationCheck, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    invalidation::detect_invalidated(&client, &config).await
+}
+
+/// 历次检查发现的失效视频及其失效前的标题
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_invalidated_history() -> Result<Vec<InvalidatedVideo>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    invalidation::load_history(&config.up_mid)
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_availability_report() -> Result<AvailabilityReport, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    availability::load_report(&config.up_mid)
+}
+
+/// 按上次检查的结果删除指定分类的视频
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn clean_by_availability(categories: Vec<Availability>) -> Result<AvailabilityCleanResult, String> {
+    if categories.contains(&Availability::Available) {
+        return Err("不能清理可正常观看的视频".to_string());
+    }
+    // 未知分类包含请求失败和触发风控的视频，这些视频可能可以正常观看
+    if categories.contains(&Availability::Unknown) {
+        return Err("不能清理检查结果未知的视频，请重新检查".to_string());
+    }
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    let mut report = availability::load_report(&config.up_mid)?;
+
+    let mut by_folder: BTreeMap<i64, Vec<ResourceId>> = BTreeMap::new();
+    for entry in report.entries.iter().filter(|e| categories.contains(&e.status)) {
+        by_folder.entry(entry.folder_id).or_default().push(entry.resource_id());
+    }
+
+    let cache = FavoritesCache::new(&config.up_mid);
+    let mut removed_count = 0;
+    let mut failures = Vec::new();
+    for (folder_id, resources) in by_folder {
+        match client.delete_resources(&resources, folder_id, &config).await {
+            Ok(count) => {
+                removed_count += count;
+                report.remove(folder_id, &resources);
+                let _ = cache.update_index(|index| index.remove_resources(folder_id, &resources));
+            }
+            Err(error) => failures.push(FolderFailure { folder_id, error }),
+        }
+        cache.invalidate(&[folder_id]);
+    }
+
+    availability::save_report(&config.up_mid, &report)?;
+    Ok(AvailabilityCleanResult { removed_count, failures })
+}
+
+// 快照相关命令
+/// 保存全部收藏夹及其内容的快照
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn take_snapshot() -> Result<SnapshotSummary, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    // 快照记录的是当前状态，不使用缓存
+    let folders = client.get_favorite_folders(&config).await?;
+
+    let mut scanned = Vec::new();
+    for folder in folders {
+        let resources = client.get_favorite_videos(folder.id, folder.media_count, &config).await?;
+        scanned.push((folder, resources));
+    }
+
+    let snapshot = Snapshot::new(scanned.clone());
+    snapshot::save_snapshot(&config.up_mid, &snapshot)?;
+
+    let cache = FavoritesCache::new(&config.up_mid);
+    cache.save_folders(&scanned.iter().map(|(folder, _)| folder.clone()).collect::<Vec<_>>())?;
+    for (folder, resources) in &scanned {
+        cache.save_resources(folder, resources, true)?;
+    }
+    Ok(snapshot.summary())
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn list_snapshots() -> Result<Vec<SnapshotSummary>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    snapshot::snapshot_ids(&config.up_mid).into_iter()
+        .map(|id| snapshot::load_snapshot(&config.up_mid, id).map(|s| s.summary()))
+        .collect()
+}
+
+/// 比较两个快照，`from` 为较早的快照
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn diff_snapshots(from: i64, to: i64) -> Result<SnapshotDiff, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let old = snapshot::load_snapshot(&config.up_mid, from)?;
+    let new = snapshot::load_snapshot(&config.up_mid, to)?;
+    Ok(snapshot::diff(&old, &new))
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn delete_snapshot(id: i64) -> Result<(), String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    snapshot::delete_snapshot(&config.up_mid, id)
+}
+
 // 移动视频相关命令
-#[tauri::command]
-async fn get_move_favorites() -> Result<Vec<FavoriteFolder>, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_move_favorites() -> Result<Vec<FavoriteFolder>, String> {
     get_favorites().await
 }
 
-#[tauri::command]
-async fn get_move_videos(source_id: i64, target_id: i64) -> Result<Vec<FavoriteResource>, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_move_videos(source_id: i64, target_id: i64) -> Result<Vec<FavoriteResource>, String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
+            let client = BilibiliClient::from_config(&config)?;
             
             // 获取源收藏夹信息
             let src_info = client.get_favorite_resources(source_id, &config).await?;
@@ -191,8 +918,7 @@ async fn get_move_videos(source_id: i64, target_id: i64) -> Result<Vec<FavoriteR
             let tar_count = tar_info.info.media_count;
             
             // 计算可移动的视频数量
-            let max_favorites_limit = 1000;
-            let available_space = max_favorites_limit - tar_count;
+            let available_space = MAX_FAVORITES_LIMIT - tar_count;
             
             if available_space <= 0 {
                 return Ok(vec![]); // 目标收藏夹已满
@@ -207,90 +933,112 @@ async fn get_move_videos(source_id: i64, target_id: i64) -> Result<Vec<FavoriteR
     }
 }
 
-#[tauri::command]
-async fn move_videos(source_id: i64, target_id: i64, video_ids: Vec<i64>) -> Result<MoveResult, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn plan_move(source_id: i64, target_id: i64, filter: MoveFilter, direction: MoveDirection) -> Result<MoveCalculateResult, String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
-            
-            // 获取源收藏夹信息
+            let client = BilibiliClient::from_config(&config)?;
+
             let src_info = client.get_favorite_resources(source_id, &config).await?;
-            
-            // 获取目标收藏夹信息
             let tar_info = client.get_favorite_resources(target_id, &config).await?;
-            
-            // 计算可移动的视频数量
-            let max_favorites_limit = 1000;
-            let available_space = max_favorites_limit - tar_info.info.media_count;
-            
-            if available_space <= 0 {
-                return Ok(MoveResult {
-                    src_media_id: source_id,
-                    tar_media_id: target_id,
-                    moved_count: 0,
-                    success_count: 0,
-                    fail_count: 0,
-                    message: Some("目标收藏夹已满（1000个视频上限）".to_string()),
-                    videos: Some(vec![]),
-                });
+
+            // 按筛选条件选出候选视频
+            let all_videos = client.get_favorite_videos(source_id, src_info.info.media_count, &config).await?;
+            let mut videos: Vec<FavoriteResource> = all_videos.into_iter().filter(|v| filter.matches(v)).collect();
+            let matched_count = videos.len() as i32;
+
+            direction.sort(&mut videos);
+
+            // 按目标收藏夹剩余容量截取
+            videos.truncate(tar_info.info.available_space() as usize);
+            let movable_count = videos.len() as i32;
+
+            Ok(MoveCalculateResult {
+                src_folder: src_info.info,
+                tar_folder: tar_info.info,
+                matched_count,
+                movable_count,
+                can_move: movable_count > 0,
+                videos,
+            })
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn move_videos(source_id: i64, target_id: i64, video_ids: Vec<i64>) -> Result<MoveResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let result = client.move_selected_videos(&video_ids, source_id, target_id, &config).await;
+            let cache = FavoritesCache::new(&config.up_mid);
+            cache.invalidate(&[source_id, target_id]);
+            if let Ok(MoveResult { success_count: 1.., videos: Some(videos), .. }) = &result {
+                let moved: Vec<ResourceId> = videos.iter()
+                    .map(ResourceId::from)
+                    .collect();
+                let _ = cache.update_index(|index| index.move_resources(source_id, target_id, &moved));
             }
-            
-            let movable_count = std::cmp::min(available_space, src_info.info.media_count);
-            let actual_count = std::cmp::min(movable_count, video_ids.len() as i32);
-            
-            if actual_count == 0 {
-                return Ok(MoveResult {
-                    src_media_id: source_id,
-                    tar_media_id: target_id,
-                    moved_count: 0,
-                    success_count: 0,
-                    fail_count: 0,
-                    message: Some("源收藏夹中没有视频".to_string()),
-                    videos: Some(vec![]),
-                });
+            result
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn copy_resources(source_id: i64, target_id: i64, resources: Vec<ResourceId>) -> Result<ResourceBatchResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let outcome = client.copy_resources(&resources, source_id, target_id, &config).await;
+            let cache = FavoritesCache::new(&config.up_mid);
+            cache.invalidate(&[target_id]);
+            if outcome.is_ok() {
+                let _ = cache.update_index(|index| index.copy_resources(source_id, target_id, &resources));
             }
-            
-            // 使用前端传递的视频ID列表
-            let actual_video_ids = video_ids.iter().take(actual_count as usize).cloned().collect::<Vec<_>>();
-            
-            // 获取这些视频的详细信息
-            let mut video_infos = Vec::new();
-            for video_id in &actual_video_ids {
-                // 获取源收藏夹的所有视频，找到匹配的视频
-                let all_videos = client.get_favorite_videos(source_id, src_info.info.media_count, &config).await?;
-                if let Some(video) = all_videos.iter().find(|v| v.id == *video_id) {
-                    video_infos.push(MoveVideoInfo {
-                        id: video.id,
-                        title: video.title.clone(),
-                        bvid: video.bvid.clone(),
-                        upper: video.upper.as_ref().map(|u| u.name.clone()),
-                        duration: video.duration,
-                    });
-                }
+            match outcome {
+                Ok(count) => Ok(ResourceBatchResult {
+                    success: true,
+                    count,
+                    message: format!("成功复制 {} 个内容", count),
+                }),
+                Err(error) => Ok(ResourceBatchResult {
+                    success: false,
+                    count: 0,
+                    message: error,
+                }),
             }
-            
-            // 移动视频
-            match client.move_videos(&actual_video_ids, source_id, target_id, &config).await {
-                Ok(_) => {
-                    Ok(MoveResult {
-                        src_media_id: source_id,
-                        tar_media_id: target_id,
-                        moved_count: actual_count,
-                        success_count: actual_count,
-                        fail_count: 0,
-                        message: Some(format!("成功移动 {} 个视频", actual_count)),
-                        videos: Some(video_infos),
-                    })
-                }
-                Err(error) => Ok(MoveResult {
-                    src_media_id: source_id,
-                    tar_media_id: target_id,
-                    moved_count: 0,
-                    success_count: 0,
-                    fail_count: actual_count,
-                    message: Some(error),
-                    videos: Some(vec![]),
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn delete_resources(media_id: i64, resources: Vec<ResourceId>) -> Result<ResourceBatchResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let outcome = client.delete_resources(&resources, media_id, &config).await;
+            let cache = FavoritesCache::new(&config.up_mid);
+            cache.invalidate(&[media_id]);
+            if outcome.is_ok() {
+                let _ = cache.update_index(|index| index.remove_resources(media_id, &resources));
+            }
+            match outcome {
+                Ok(count) => Ok(ResourceBatchResult {
+                    success: true,
+                    count,
+                    message: format!("成功删除 {} 个内容", count),
+                }),
+                Err(error) => Ok(ResourceBatchResult {
+                    success: false,
+                    count: 0,
+                    message: error,
                 }),
             }
         }
@@ -299,17 +1047,17 @@ async fn move_videos(source_id: i64, target_id: i64, video_ids: Vec<i64>) -> Res
 }
 
 // 排序相关命令
-#[tauri::command]
-async fn get_sort_folders() -> Result<Vec<FavoriteFolder>, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_sort_folders() -> Result<Vec<FavoriteFolder>, String> {
     get_favorites().await
 }
 
-#[tauri::command]
-async fn execute_sort(folder_ids: Vec<i64>) -> Result<SortResult, String> {
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn execute_sort(folder_ids: Vec<i64>) -> Result<SortResult, String> {
     let config = load_config()?;
     match config {
         Some(config) => {
-            let client = BilibiliClient::new();
+            let client = BilibiliClient::from_config(&config)?;
             
             // 获取所有收藏夹信息
             let all_folders = client.get_favorite_folders(&config).await?;
@@ -357,7 +1105,9 @@ async fn execute_sort(folder_ids: Vec<i64>) -> Result<SortResult, String> {
             }
             
             // 执行排序
-            match client.sort_favorite_folders(&folder_ids, &config).await {
+            let outcome = client.sort_favorite_folders(&folder_ids, &config).await;
+            FavoritesCache::new(&config.up_mid).invalidate(&[]);
+            match outcome {
                 Ok(_) => Ok(SortResult {
                     success: true,
                     message: format!("成功排序 {} 个收藏夹", folder_ids.len()),
@@ -376,28 +1126,240 @@ async fn execute_sort(folder_ids: Vec<i64>) -> Result<SortResult, String> {
     }
 }
 
+#[cfg(feature = "desktop")]
+#[tauri::command]
+async fn sort_folder_resources(
+    app: AppHandle,
+    media_id: i64,
+    resource_ids: Option<Vec<i64>>,
+    sort_key: Option<bilibili::ResourceSortKey>,
+    descending: bool,
+) -> Result<ResourceSortResult, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+
+            // 获取收藏夹内全部视频
+            let info = client.get_favorite_resources(media_id, &config).await?;
+            let mut resources = client.get_favorite_videos(media_id, info.info.media_count, &config).await?;
+
+            if let Some(ids) = resource_ids {
+                // 指定顺序：列出的视频在前，其余视频保持原有相对顺序
+                let invalid_ids: Vec<_> = ids.iter().filter(|id| !resources.iter().any(|r| r.id == **id)).collect();
+                if !invalid_ids.is_empty() {
+                    return Ok(ResourceSortResult {
+                        media_id,
+                        success: false,
+                        message: "排序列表包含不在该收藏夹中的视频ID".to_string(),
+                        sorted_count: 0,
+                    });
+                }
+                resources.sort_by_key(|r| ids.iter().position(|id| *id == r.id).unwrap_or(ids.len()));
+            } else if let Some(key) = sort_key {
+                key.sort(&mut resources, descending);
+            } else {
+                return Err("请指定排序方式或视频顺序".to_string());
+            }
+
+            let order: Vec<ResourceId> = resources.iter().map(ResourceId::from).collect();
+            let total = order.len();
+
+            let outcome = client.sort_favorite_resources(media_id, &order, &config, |processed, total| {
+                let _ = app.emit("resource-sort-progress", ResourceSortProgress { media_id, processed, total });
+            }).await;
+            FavoritesCache::new(&config.up_mid).invalidate(&[media_id]);
+            match outcome {
+                Ok(_) => Ok(ResourceSortResult {
+                    media_id,
+                    success: true,
+                    message: format!("成功排序 {} 个视频", total),
+                    sorted_count: total,
+                }),
+                Err(error) => Ok(ResourceSortResult {
+                    media_id,
+                    success: false,
+                    message: error,
+                    sorted_count: 0,
+                }),
+            }
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+// 收藏夹整理相关命令
+/// 将多个收藏夹的内容合并到目标收藏夹，`delete_emptied` 为 true 时删除清空后的源收藏夹
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn merge_folders(source_ids: Vec<i64>, target_id: i64, delete_emptied: bool) -> Result<MergeReport, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    organize::merge_folders(&client, &config, &source_ids, target_id, delete_emptied).await
+}
+
+/// 预览拆分结果，不修改任何收藏夹
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn preview_split_folder(media_id: i64, criterion: SplitCriterion, name_template: String, min_count: Option<usize>) -> Result<SplitPreview, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    organize::preview_split(&client, &config, media_id, &criterion, &name_template, min_count.unwrap_or(1)).await
+}
+
+/// 按年份、季度、UP主、分区或时长拆分收藏夹，数量少于 `min_count` 的分组留在原收藏夹
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn split_folder(media_id: i64, criterion: SplitCriterion, name_template: String, min_count: Option<usize>) -> Result<SplitReport, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    organize::split_folder(&client, &config, media_id, &criterion, &name_template, min_count.unwrap_or(1)).await
+}
+
+/// 预览批量重命名的结果，不修改任何收藏夹
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn preview_rename_folders(media_ids: Vec<i64>, rule: RenameRule) -> Result<Vec<RenameItem>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    organize::preview_rename(&client, &config, &media_ids, &rule).await
+}
+
+/// 按模板或正则批量重命名收藏夹，有重名或名称无效时不执行
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn rename_folders(media_ids: Vec<i64>, rule: RenameRule) -> Result<Vec<RenameResult>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    organize::rename_folders(&client, &config, &media_ids, &rule).await
+}
+
+/// 删除收藏夹，默认收藏夹无法删除
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn delete_favorite_folders(media_ids: Vec<i64>) -> Result<(), String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    let folders = cached_folders(&config, true).await?;
+    if folders.iter().any(|f| media_ids.contains(&f.id) && client.is_default_folder(f)) {
+        return Err("默认收藏夹无法删除".to_string());
+    }
+
+    client.delete_folders(&media_ids, &config).await?;
+    FavoritesCache::new(&config.up_mid).invalidate(&media_ids);
+    Ok(())
+}
+
+// 公开状态相关命令
+/// 列出公开的收藏夹，每个收藏夹附带最多 `sample_size` 个示例标题
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn audit_folder_privacy(sample_size: Option<usize>) -> Result<PrivacyAudit, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    privacy::audit(&client, &config, sample_size.unwrap_or(privacy::DEFAULT_SAMPLE_SIZE)).await
+}
+
+/// 批量将收藏夹设为公开或私密
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn set_folders_privacy(media_ids: Vec<i64>, private: bool) -> Result<Vec<PrivacyChangeResult>, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    privacy::set_privacy(&client, &config, &media_ids, private).await
+}
+
+// 统计相关命令
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub async fn get_favorites_stats(top_n: Option<usize>) -> Result<FavoritesStats, String> {
+    let config = load_config()?;
+    match config {
+        Some(config) => {
+            let client = BilibiliClient::from_config(&config)?;
+            let folders = client.get_favorite_folders(&config).await?;
+
+            let mut scanned = Vec::new();
+            for folder in folders {
+                let resources = client.get_favorite_videos(folder.id, folder.media_count, &config).await?;
+                scanned.push((folder, resources));
+            }
+
+            Ok(stats::compute_stats(&scanned, top_n.unwrap_or(20)))
+        }
+        None => Err("请先配置Cookie".to_string())
+    }
+}
+
+#[cfg(feature = "desktop")]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            schedule::start(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // 配置相关
             get_config,
             save_config_command,
             delete_config_command,
+            get_header_presets,
+            verify_account,
+            // Cookie相关
+            parse_cookies,
+            convert_cookies,
+            import_cookies,
+            get_browser_profiles,
+            import_browser_cookies,
+            // 定时清理相关
+            get_schedule_config,
+            save_schedule_config,
+            get_schedule_log,
             // 收藏夹相关
             get_favorites,
             get_favorite_details,
+            get_folder_resources,
+            scan_favorites,
+            search_favorites,
+            search_favorites_online,
+            check_availability,
+            get_availability_report,
+            clean_by_availability,
+            detect_invalidated_videos,
+            get_invalidated_history,
+            merge_folders,
+            delete_favorite_folders,
+            preview_split_folder,
+            split_folder,
+            preview_rename_folders,
+            rename_folders,
+            // 公开状态相关
+            audit_folder_privacy,
+            set_folders_privacy,
+            take_snapshot,
+            list_snapshots,
+            diff_snapshots,
+            delete_snapshot,
+            clear_favorites_cache,
+            // 收藏和订阅相关
+            get_collected_folders,
+            unsubscribe_collected,
+            // 稍后再看相关
+            get_watch_later,
+            add_to_watch_later,
+            delete_from_watch_later,
+            move_watch_later_to_folder,
+            clean_watch_later,
             // 清理相关
             clean_favorite,
             clean_multiple_favorites,
             // 移动相关
             get_move_favorites,
             get_move_videos,
+            plan_move,
             move_videos,
+            copy_resources,
+            delete_resources,
             // 排序相关
             get_sort_folders,
             execute_sort,
+            sort_folder_resources,
+            // 统计相关
+            get_favorites_stats,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
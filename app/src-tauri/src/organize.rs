@@ -0,0 +1,691 @@
+//! 收藏夹整理：合并、拆分、批量重命名收藏夹
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use chrono::{Datelike, Local, TimeZone};
+use regex::Regex;
+use crate::bilibili::{BilibiliClient, FavoriteFolder, FavoriteResource, ResourceId, MAX_FAVORITES_LIMIT, MAX_FOLDER_TITLE_LENGTH, RESOURCE_BATCH_SIZE};
+use crate::cache::FavoritesCache;
+use crate::config::AppConfig;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeSourceResult {
+    pub folder_id: i64,
+    pub title: String,
+    pub moved_count: usize,
+    /// 目标收藏夹中已有、直接从源收藏夹删除的内容
+    pub duplicate_count: usize,
+    /// 目标收藏夹已满而留在源收藏夹的内容
+    pub skipped_count: usize,
+    pub failed_count: usize,
+    /// 源收藏夹是否已清空
+    pub emptied: bool,
+    pub deleted: bool,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub target_id: i64,
+    pub target_title: String,
+    pub moved_count: usize,
+    pub duplicate_count: usize,
+    pub skipped_count: usize,
+    pub failed_count: usize,
+    pub sources: Vec<MergeSourceResult>,
+}
+
+/// 将多个源收藏夹的内容依次移动到目标收藏夹
+///
+/// 目标收藏夹中已有的内容不再移动，而是从源收藏夹删除；目标收藏夹装满后剩余内容留在源收藏夹。
+/// `delete_emptied` 为 true 时删除已清空的源收藏夹（默认收藏夹除外）。
+pub async fn merge_folders(client: &BilibiliClient, config: &AppConfig, source_ids: &[i64], target_id: i64, delete_emptied: bool) -> Result<MergeReport, String> {
+    if source_ids.is_empty() {
+        return Err("请选择要合并的收藏夹".to_string());
+    }
+    if source_ids.contains(&target_id) {
+        return Err("目标收藏夹不能同时作为源收藏夹".to_string());
+    }
+
+    let folders = client.get_favorite_folders(config).await?;
+    let find = |id: i64| folders.iter().find(|f| f.id == id).cloned()
+        .ok_or_else(|| format!("收藏夹 {} 不存在", id));
+    let target = find(target_id)?;
+    let sources: Vec<FavoriteFolder> = source_ids.iter().map(|id| find(*id)).collect::<Result<_, _>>()?;
+
+    let mut present: HashSet<ResourceId> = client.get_favorite_videos(target.id, target.media_count, config).await?
+        .iter()
+        .map(ResourceId::from)
+        .collect();
+    let mut capacity = target.available_space().max(0) as usize;
+
+    let cache = FavoritesCache::new(&config.up_mid);
+    let mut results = Vec::new();
+    for source in &sources {
+        let resources = match client.get_favorite_videos(source.id, source.media_count, config).await {
+            Ok(resources) => resources,
+            Err(error) => {
+                // 获取失败的源收藏夹跳过，不影响其他源收藏夹
+                results.push(MergeSourceResult {
+                    folder_id: source.id,
+                    title: source.title.clone(),
+                    moved_count: 0,
+                    duplicate_count: 0,
+                    skipped_count: 0,
+                    failed_count: 0,
+                    emptied: false,
+                    deleted: false,
+                    errors: vec![format!("获取收藏夹内容失败: {}", error)],
+                });
+                continue;
+            }
+        };
+        let (duplicates, candidates): (Vec<ResourceId>, Vec<ResourceId>) = resources.iter()
+            .map(ResourceId::from)
+            .partition(|id| present.contains(id));
+        let to_move: Vec<ResourceId> = candidates.iter().take(capacity).copied().collect();
+
+        let mut result = MergeSourceResult {
+            folder_id: source.id,
+            title: source.title.clone(),
+            moved_count: 0,
+            duplicate_count: 0,
+            skipped_count: candidates.len() - to_move.len(),
+            failed_count: 0,
+            emptied: false,
+            deleted: false,
+            errors: Vec::new(),
+        };
+
+        for batch in to_move.chunks(RESOURCE_BATCH_SIZE) {
+            match client.move_videos(batch, source.id, target.id, config).await {
+                Ok(_) => {
+                    result.moved_count += batch.len();
+                    present.extend(batch.iter().copied());
+                    let _ = cache.update_index(|index| index.move_resources(source.id, target.id, batch));
+                }
+                Err(error) => {
+                    result.failed_count += batch.len();
+                    result.errors.push(error);
+                }
+            }
+        }
+        capacity -= result.moved_count;
+
+        for batch in duplicates.chunks(RESOURCE_BATCH_SIZE) {
+            match client.delete_resources(batch, source.id, config).await {
+                Ok(_) => {
+                    result.duplicate_count += batch.len();
+                    let _ = cache.update_index(|index| index.remove_resources(source.id, batch));
+                }
+                Err(error) => {
+                    result.failed_count += batch.len();
+                    result.errors.push(error);
+                }
+            }
+        }
+
+        result.emptied = result.moved_count + result.duplicate_count == resources.len();
+        if delete_emptied && result.emptied && !client.is_default_folder(source) {
+            match client.delete_folders(&[source.id], config).await {
+                Ok(_) => result.deleted = true,
+                Err(error) => result.errors.push(format!("删除收藏夹失败: {}", error)),
+            }
+        }
+
+        results.push(result);
+    }
+
+    let mut changed: Vec<i64> = source_ids.to_vec();
+    changed.push(target.id);
+    cache.invalidate(&changed);
+
+    Ok(MergeReport {
+        target_id: target.id,
+        target_title: target.title,
+        moved_count: results.iter().map(|r| r.moved_count).sum(),
+        duplicate_count: results.iter().map(|r| r.duplicate_count).sum(),
+        skipped_count: results.iter().map(|r| r.skipped_count).sum(),
+        failed_count: results.iter().map(|r| r.failed_count).sum(),
+        sources: results,
+    })
+}
+
+/// 模板变量的值，数字可以用 `{name:02}` 指定补零宽度
+pub enum TemplateValue {
+    Text(String),
+    Number(i64),
+}
+
+/// 渲染收藏夹名称模板，变量写作 `{name}` 或 `{name:02}`，`{{` 和 `}}` 表示花括号本身
+pub fn render_template(template: &str, lookup: impl Fn(&str) -> Option<TemplateValue>) -> Result<String, String> {
+    let mut output = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => spec.push(c),
+                        None => return Err("模板中的花括号没有闭合".to_string()),
+                    }
+                }
+
+                let (name, width) = match spec.split_once(':') {
+                    Some((name, width)) => {
+                        let width: usize = width.parse()
+                            .map_err(|_| format!("模板变量 {{{}}} 的宽度无效", spec))?;
+                        (name.trim(), width)
+                    }
+                    None => (spec.trim(), 0),
+                };
+                match lookup(name) {
+                    Some(TemplateValue::Number(value)) => output.push_str(&format!("{:0width$}", value, width = width)),
+                    Some(TemplateValue::Text(value)) => output.push_str(&value),
+                    None => return Err(format!("模板中的变量 {{{}}} 无效", name)),
+                }
+            }
+            c => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+/// 检查收藏夹标题是否可用
+pub fn validate_folder_title(title: &str) -> Result<(), String> {
+    let length = title.chars().count();
+    if title.trim().is_empty() {
+        Err("收藏夹名称不能为空".to_string())
+    } else if length > MAX_FOLDER_TITLE_LENGTH {
+        Err(format!("收藏夹名称「{}」超过 {} 个字符", title, MAX_FOLDER_TITLE_LENGTH))
+    } else {
+        Ok(())
+    }
+}
+
+/// 拆分收藏夹的依据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SplitCriterion {
+    /// 按收藏时间的年份
+    Year,
+    /// 按收藏时间的季度
+    Quarter,
+    /// 按UP主
+    Uploader,
+    /// 按视频分区（tid）
+    Partition,
+    /// 按时长分段，`bounds` 为各段的上限（分钟），为空时使用 5、20、60 分钟
+    Duration {
+        #[serde(default)]
+        bounds: Vec<i32>,
+    },
+}
+
+const DEFAULT_DURATION_BOUNDS: [i32; 3] = [5, 20, 60];
+
+/// 拆分后的一组内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitGroup {
+    /// 分组名称，如 2023、2023Q1、UP主名称、分区名称或时长范围
+    pub label: String,
+    /// 由模板生成的目标收藏夹名称
+    pub folder_title: String,
+    pub count: usize,
+    /// 已有同名收藏夹时移动到该收藏夹
+    pub existing_folder_id: Option<i64>,
+    /// 数量少于 `min_count` 的分组留在原收藏夹
+    pub stays: bool,
+    /// 名称无效或目标收藏夹容量不足等问题
+    pub error: Option<String>,
+    #[serde(skip)]
+    resources: Vec<ResourceId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitPreview {
+    pub folder_id: i64,
+    pub folder_title: String,
+    /// 新建的收藏夹沿用原收藏夹的公开状态
+    pub folder_private: bool,
+    pub total: usize,
+    pub groups: Vec<SplitGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitGroupResult {
+    pub label: String,
+    pub folder_id: Option<i64>,
+    pub folder_title: String,
+    pub created: bool,
+    pub moved_count: usize,
+    pub failed_count: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitReport {
+    pub folder_id: i64,
+    pub moved_count: usize,
+    pub failed_count: usize,
+    pub groups: Vec<SplitGroupResult>,
+}
+
+fn duration_label(minutes: &[i32], duration: i32) -> (i64, String) {
+    let minute = duration / 60;
+    match minutes.iter().position(|bound| minute < *bound) {
+        Some(0) => (0, format!("{}分钟以内", minutes[0])),
+        Some(index) => (index as i64, format!("{}-{}分钟", minutes[index - 1], minutes[index])),
+        None => (minutes.len() as i64, format!("{}分钟以上", minutes[minutes.len() - 1])),
+    }
+}
+
+/// 按依据分组，返回 (分组名称, 内容) 的列表
+async fn group_resources(client: &BilibiliClient, config: &AppConfig, folder: &FavoriteFolder, resources: &[FavoriteResource], criterion: &SplitCriterion) -> Result<Vec<(String, Vec<ResourceId>)>, String> {
+    let mut groups: BTreeMap<(i64, String), Vec<ResourceId>> = BTreeMap::new();
+    let fav_date = |r: &FavoriteResource| Local.timestamp_opt(r.fav_time, 0).single();
+
+    match criterion {
+        SplitCriterion::Year | SplitCriterion::Quarter => {
+            for resource in resources {
+                let Some(date) = fav_date(resource) else { continue };
+                let (key, label) = match criterion {
+                    SplitCriterion::Year => (date.year() as i64, date.year().to_string()),
+                    _ => {
+                        let quarter = date.month0() / 3 + 1;
+                        (date.year() as i64 * 10 + quarter as i64, format!("{}Q{}", date.year(), quarter))
+                    }
+                };
+                groups.entry((key, label)).or_default().push(ResourceId::from(resource));
+            }
+        }
+        SplitCriterion::Uploader => {
+            for resource in resources {
+                let (mid, name) = resource.upper.as_ref()
+                    .map(|u| (u.mid, u.name.clone()))
+                    .unwrap_or((0, "未知UP主".to_string()));
+                groups.entry((mid, name)).or_default().push(ResourceId::from(resource));
+            }
+        }
+        SplitCriterion::Partition => {
+            // 收藏夹内容不包含分区信息，需要按分区筛选获取
+            let mut assigned = HashSet::new();
+            for partition in client.get_folder_partitions(folder.id, config).await? {
+                let ids: Vec<ResourceId> = client.get_partition_resources(folder.id, partition.tid, config).await?
+                    .iter()
+                    .map(ResourceId::from)
+                    .filter(|id| assigned.insert(*id))
+                    .collect();
+                if !ids.is_empty() {
+                    groups.insert((partition.tid, partition.name), ids);
+                }
+            }
+            let others: Vec<ResourceId> = resources.iter()
+                .map(ResourceId::from)
+                .filter(|id| !assigned.contains(id))
+                .collect();
+            if !others.is_empty() {
+                groups.insert((i64::MAX, "其他".to_string()), others);
+            }
+        }
+        SplitCriterion::Duration { bounds } => {
+            let mut minutes: Vec<i32> = if bounds.is_empty() { DEFAULT_DURATION_BOUNDS.to_vec() } else { bounds.clone() };
+            minutes.sort();
+            minutes.dedup();
+            for resource in resources {
+                groups.entry(duration_label(&minutes, resource.duration)).or_default().push(ResourceId::from(resource));
+            }
+        }
+    }
+
+    let mut groups: Vec<(String, Vec<ResourceId>)> = groups.into_iter().map(|((_, label), ids)| (label, ids)).collect();
+    // UP主和分区按数量从多到少排列，时间和时长按自身顺序排列
+    if matches!(criterion, SplitCriterion::Uploader | SplitCriterion::Partition) {
+        groups.sort_by_key(|g| std::cmp::Reverse(g.1.len()));
+    }
+    Ok(groups)
+}
+
+/// 计算拆分方案，不修改任何收藏夹
+///
+/// 名称模板可使用 `{title}`（原收藏夹名称）、`{group}`（分组名称）、`{count}` 和 `{index}`（从 1 开始的序号）。
+pub async fn preview_split(client: &BilibiliClient, config: &AppConfig, media_id: i64, criterion: &SplitCriterion, name_template: &str, min_count: usize) -> Result<SplitPreview, String> {
+    let folders = client.get_favorite_folders(config).await?;
+    let folder = folders.iter().find(|f| f.id == media_id)
+        .ok_or_else(|| "收藏夹不存在".to_string())?;
+    let resources = client.get_favorite_videos(folder.id, folder.media_count, config).await?;
+
+    let mut groups = Vec::new();
+    let mut titles = HashSet::new();
+    for (index, (label, ids)) in group_resources(client, config, folder, &resources, criterion).await?.into_iter().enumerate() {
+        let rendered = render_template(name_template, |name| match name {
+            "title" => Some(TemplateValue::Text(folder.title.clone())),
+            "group" => Some(TemplateValue::Text(label.clone())),
+            "count" => Some(TemplateValue::Number(ids.len() as i64)),
+            "index" => Some(TemplateValue::Number(index as i64 + 1)),
+            _ => None,
+        })?;
+        let folder_title = rendered.trim().to_string();
+
+        let existing = folders.iter().find(|f| f.title == folder_title);
+        let stays = ids.len() < min_count || existing.is_some_and(|f| f.id == folder.id);
+        let error = if stays {
+            None
+        } else if !titles.insert(folder_title.clone()) {
+            Some(format!("与其他分组的收藏夹名称「{}」重复", folder_title))
+        } else if let Some(existing) = existing.filter(|f| f.available_space() < ids.len() as i32) {
+            Some(format!("已有收藏夹「{}」剩余容量不足（{}/{}）", existing.title, existing.media_count, MAX_FAVORITES_LIMIT))
+        } else {
+            validate_folder_title(&folder_title).err()
+        };
+
+        groups.push(SplitGroup {
+            label,
+            folder_title,
+            count: ids.len(),
+            existing_folder_id: existing.map(|f| f.id),
+            stays,
+            error,
+            resources: ids,
+        });
+    }
+
+    Ok(SplitPreview {
+        folder_id: folder.id,
+        folder_title: folder.title.clone(),
+        folder_private: folder.is_private(),
+        total: resources.len(),
+        groups,
+    })
+}
+
+/// 按拆分方案新建收藏夹并移动内容，方案中有错误时不执行任何操作
+pub async fn split_folder(client: &BilibiliClient, config: &AppConfig, media_id: i64, criterion: &SplitCriterion, name_template: &str, min_count: usize) -> Result<SplitReport, String> {
+    let preview = preview_split(client, config, media_id, criterion, name_template, min_count).await?;
+    let errors: Vec<String> = preview.groups.iter().filter_map(|g| g.error.clone()).collect();
+    if !errors.is_empty() {
+        return Err(errors.join("；"));
+    }
+
+    let cache = FavoritesCache::new(&config.up_mid);
+    let mut results = Vec::new();
+    let mut changed = vec![media_id];
+
+    for group in preview.groups.into_iter().filter(|g| !g.stays) {
+        let mut result = SplitGroupResult {
+            label: group.label,
+            folder_id: group.existing_folder_id,
+            folder_title: group.folder_title,
+            created: false,
+            moved_count: 0,
+            failed_count: 0,
+            errors: Vec::new(),
+        };
+
+        let target_id = match group.existing_folder_id {
+            Some(id) => id,
+            None => match client.create_folder(&result.folder_title, "", preview.folder_private, config).await {
+                Ok(folder) => {
+                    result.folder_id = Some(folder.id);
+                    result.created = true;
+                    folder.id
+                }
+                Err(error) => {
+                    result.failed_count = group.count;
+                    result.errors.push(format!("新建收藏夹失败: {}", error));
+                    results.push(result);
+                    continue;
+                }
+            },
+        };
+        changed.push(target_id);
+
+        for batch in group.resources.chunks(RESOURCE_BATCH_SIZE) {
+            match client.move_videos(batch, media_id, target_id, config).await {
+                Ok(_) => {
+                    result.moved_count += batch.len();
+                    let _ = cache.update_index(|index| index.move_resources(media_id, target_id, batch));
+                }
+                Err(error) => {
+                    result.failed_count += batch.len();
+                    result.errors.push(error);
+                }
+            }
+        }
+        results.push(result);
+    }
+
+    cache.invalidate(&changed);
+
+    Ok(SplitReport {
+        folder_id: media_id,
+        moved_count: results.iter().map(|r| r.moved_count).sum(),
+        failed_count: results.iter().map(|r| r.failed_count).sum(),
+        groups: results,
+    })
+}
+
+/// 批量重命名的规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RenameRule {
+    /// 名称模板，可使用 `{title}`、`{index}`（按选择顺序从 1 开始）、`{year}`（创建年份）、`{count}` 和 `{id}`
+    Template { template: String },
+    /// 正则查找替换，`replacement` 中可用 `$1`、`${name}` 引用捕获组
+    Regex { pattern: String, replacement: String },
+}
+
+impl RenameRule {
+    fn uses_year(&self) -> bool {
+        matches!(self, RenameRule::Template { template } if template.contains("{year"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameItem {
+    pub folder_id: i64,
+    pub old_title: String,
+    pub new_title: String,
+    /// 新名称与原名称相同时为 false，执行时跳过
+    pub changed: bool,
+    /// 名称为空、过长、与其他收藏夹重名或默认收藏夹等问题
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameResult {
+    pub folder_id: i64,
+    pub old_title: String,
+    pub new_title: String,
+    pub renamed: bool,
+    pub error: Option<String>,
+}
+
+/// 计算重命名结果，不修改任何收藏夹
+///
+/// 新名称与未选中收藏夹的名称或其他收藏夹的新名称相同时记为重名。
+pub async fn preview_rename(client: &BilibiliClient, config: &AppConfig, media_ids: &[i64], rule: &RenameRule) -> Result<Vec<RenameItem>, String> {
+    if media_ids.is_empty() {
+        return Err("请选择要重命名的收藏夹".to_string());
+    }
+
+    let regex = match rule {
+        RenameRule::Regex { pattern, .. } => Some(Regex::new(pattern)
+            .map_err(|e| format!("正则表达式无效: {}", e))?),
+        RenameRule::Template { .. } => None,
+    };
+
+    let folders = client.get_favorite_folders(config).await?;
+    let mut items = Vec::new();
+    for (index, media_id) in media_ids.iter().enumerate() {
+        let folder = folders.iter().find(|f| f.id == *media_id)
+            .ok_or_else(|| format!("收藏夹 {} 不存在", media_id))?;
+
+        let new_title = match rule {
+            RenameRule::Regex { replacement, .. } => match &regex {
+                Some(regex) => regex.replace_all(&folder.title, replacement.as_str()).into_owned(),
+                None => folder.title.clone(),
+            },
+            RenameRule::Template { template } => {
+                // 收藏夹列表接口不返回创建时间，用到年份时从收藏夹详情中获取
+                let ctime = if rule.uses_year() && folder.ctime == 0 {
+                    client.get_favorite_resources(folder.id, config).await?.info.ctime
+                } else {
+                    folder.ctime
+                };
+                let year = Local.timestamp_opt(ctime, 0).single()
+                    .map(|t| t.year() as i64)
+                    .unwrap_or_default();
+
+                render_template(template, |name| match name {
+                    "title" => Some(TemplateValue::Text(folder.title.clone())),
+                    "index" => Some(TemplateValue::Number(index as i64 + 1)),
+                    "year" => Some(TemplateValue::Number(year)),
+                    "count" => Some(TemplateValue::Number(folder.media_count as i64)),
+                    "id" => Some(TemplateValue::Number(folder.id)),
+                    _ => None,
+                })?
+            }
+        };
+        let new_title = new_title.trim().to_string();
+        let changed = new_title != folder.title;
+
+        let error = if !changed {
+            None
+        } else if client.is_default_folder(folder) {
+            Some("默认收藏夹无法重命名".to_string())
+        } else {
+            validate_folder_title(&new_title).err()
+        };
+
+        items.push(RenameItem {
+            folder_id: folder.id,
+            old_title: folder.title.clone(),
+            new_title,
+            changed,
+            error,
+        });
+    }
+
+    let mut final_titles: BTreeMap<&str, usize> = BTreeMap::new();
+    for folder in folders.iter().filter(|f| !media_ids.contains(&f.id)) {
+        *final_titles.entry(folder.title.as_str()).or_default() += 1;
+    }
+    for item in &items {
+        *final_titles.entry(item.new_title.as_str()).or_default() += 1;
+    }
+    let duplicated: HashSet<String> = final_titles.into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(title, _)| title.to_string())
+        .collect();
+
+    for item in items.iter_mut().filter(|i| i.changed && i.error.is_none()) {
+        if duplicated.contains(&item.new_title) {
+            item.error = Some(format!("与其他收藏夹的名称「{}」重复", item.new_title));
+        }
+    }
+
+    Ok(items)
+}
+
+/// 按预览结果逐个重命名收藏夹，预览中有错误时不执行任何操作
+pub async fn rename_folders(client: &BilibiliClient, config: &AppConfig, media_ids: &[i64], rule: &RenameRule) -> Result<Vec<RenameResult>, String> {
+    let items = preview_rename(client, config, media_ids, rule).await?;
+    let errors: Vec<String> = items.iter()
+        .filter_map(|i| i.error.as_ref().map(|e| format!("{}: {}", i.old_title, e)))
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors.join("；"));
+    }
+
+    let mut results = Vec::new();
+    for item in items {
+        let mut result = RenameResult {
+            folder_id: item.folder_id,
+            old_title: item.old_title,
+            new_title: item.new_title,
+            renamed: false,
+            error: None,
+        };
+
+        if item.changed {
+            match client.edit_folder(item.folder_id, &result.new_title, None, config).await {
+                Ok(_) => result.renamed = true,
+                Err(error) => result.error = Some(error),
+            }
+        }
+        results.push(result);
+    }
+
+    if results.iter().any(|r| r.renamed) {
+        FavoritesCache::new(&config.up_mid).invalidate(&[]);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<TemplateValue> {
+        match name {
+            "title" => Some(TemplateValue::Text("音乐".to_string())),
+            "index" => Some(TemplateValue::Number(3)),
+            "year" => Some(TemplateValue::Number(2023)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn renders_variables_with_padding() {
+        assert_eq!(render_template("{index:02} {title}", lookup).unwrap(), "03 音乐");
+        assert_eq!(render_template("[{year}] {title}", lookup).unwrap(), "[2023] 音乐");
+        assert_eq!(render_template("{ index }", lookup).unwrap(), "3");
+        // 宽度对文本变量无效
+        assert_eq!(render_template("{title:05}", lookup).unwrap(), "音乐");
+    }
+
+    #[test]
+    fn renders_escaped_braces() {
+        assert_eq!(render_template("{{{title}}}", lookup).unwrap(), "{音乐}");
+        assert_eq!(render_template("{{index}}", lookup).unwrap(), "{index}");
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert!(render_template("{title", lookup).is_err());
+        assert!(render_template("{unknown}", lookup).is_err());
+        assert!(render_template("{index:ab}", lookup).is_err());
+    }
+
+    #[test]
+    fn buckets_durations_at_bounds() {
+        let bounds = DEFAULT_DURATION_BOUNDS;
+        assert_eq!(duration_label(&bounds, 0), (0, "5分钟以内".to_string()));
+        assert_eq!(duration_label(&bounds, 5 * 60 - 1), (0, "5分钟以内".to_string()));
+        assert_eq!(duration_label(&bounds, 5 * 60), (1, "5-20分钟".to_string()));
+        assert_eq!(duration_label(&bounds, 20 * 60 - 1), (1, "5-20分钟".to_string()));
+        assert_eq!(duration_label(&bounds, 20 * 60), (2, "20-60分钟".to_string()));
+        assert_eq!(duration_label(&bounds, 60 * 60), (3, "60分钟以上".to_string()));
+        assert_eq!(duration_label(&[10], 3 * 3600), (1, "10分钟以上".to_string()));
+    }
+
+    #[test]
+    fn validates_title_length() {
+        assert!(validate_folder_title(&"字".repeat(MAX_FOLDER_TITLE_LENGTH)).is_ok());
+        assert!(validate_folder_title(&"字".repeat(MAX_FOLDER_TITLE_LENGTH + 1)).is_err());
+        assert!(validate_folder_title("  ").is_err());
+    }
+}
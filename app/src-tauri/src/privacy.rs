@@ -0,0 +1,112 @@
+//! 收藏夹公开状态：列出公开的收藏夹，批量设为公开或私密
+
+use serde::{Deserialize, Serialize};
+use crate::bilibili::BilibiliClient;
+use crate::cache::FavoritesCache;
+use crate::config::AppConfig;
+
+/// 每个公开收藏夹默认展示的示例标题数量
+pub const DEFAULT_SAMPLE_SIZE: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicFolder {
+    pub folder_id: i64,
+    pub title: String,
+    pub media_count: i32,
+    /// 收藏夹第一页中的部分内容标题，不含失效视频
+    pub sample_titles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyAudit {
+    pub public_count: usize,
+    pub private_count: usize,
+    /// 公开收藏夹中的内容总数
+    pub exposed_count: i32,
+    pub public_folders: Vec<PublicFolder>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyChangeResult {
+    pub folder_id: i64,
+    pub title: String,
+    /// 已经是目标状态的收藏夹不会修改
+    pub changed: bool,
+    pub error: Option<String>,
+}
+
+/// 列出全部公开的收藏夹及其内容数量和示例标题
+pub async fn audit(client: &BilibiliClient, config: &AppConfig, sample_size: usize) -> Result<PrivacyAudit, String> {
+    let folders = client.get_favorite_folders(config).await?;
+    let _ = FavoritesCache::new(&config.up_mid).save_folders(&folders);
+
+    let mut public_folders = Vec::new();
+    for folder in folders.iter().filter(|f| !f.is_private()) {
+        let sample_titles = if sample_size > 0 && folder.media_count > 0 {
+            client.get_favorite_resources(folder.id, config).await?
+                .medias
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|r| !r.is_invalid())
+                .take(sample_size)
+                .map(|r| r.title)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        public_folders.push(PublicFolder {
+            folder_id: folder.id,
+            title: folder.title.clone(),
+            media_count: folder.media_count,
+            sample_titles,
+        });
+    }
+
+    Ok(PrivacyAudit {
+        public_count: public_folders.len(),
+        private_count: folders.len() - public_folders.len(),
+        exposed_count: public_folders.iter().map(|f| f.media_count).sum(),
+        public_folders,
+    })
+}
+
+/// 将选中的收藏夹设为公开或私密，逐个修改并返回每个收藏夹的结果
+pub async fn set_privacy(client: &BilibiliClient, config: &AppConfig, media_ids: &[i64], private: bool) -> Result<Vec<PrivacyChangeResult>, String> {
+    if media_ids.is_empty() {
+        return Err("请选择要修改的收藏夹".to_string());
+    }
+
+    let folders = client.get_favorite_folders(config).await?;
+    let mut results = Vec::new();
+    for media_id in media_ids {
+        let Some(folder) = folders.iter().find(|f| f.id == *media_id) else {
+            results.push(PrivacyChangeResult {
+                folder_id: *media_id,
+                title: String::new(),
+                changed: false,
+                error: Some(format!("收藏夹 {} 不存在", media_id)),
+            });
+            continue;
+        };
+
+        let mut result = PrivacyChangeResult {
+            folder_id: folder.id,
+            title: folder.title.clone(),
+            changed: false,
+            error: None,
+        };
+        if folder.is_private() != private {
+            match client.edit_folder(folder.id, &folder.title, Some(private), config).await {
+                Ok(_) => result.changed = true,
+                Err(error) => result.error = Some(error),
+            }
+        }
+        results.push(result);
+    }
+
+    if results.iter().any(|r| r.changed) {
+        FavoritesCache::new(&config.up_mid).invalidate(&[]);
+    }
+    Ok(results)
+}
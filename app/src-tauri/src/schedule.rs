@@ -0,0 +1,381 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::{Datelike, Local, TimeZone};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use crate::bilibili::BilibiliClient;
+use crate::config::{get_config_dir, load_config, AppConfig};
+use crate::invalidation::{self, InvalidatedVideo};
+use crate::{clean_folders, CleanSummary};
+
+/// 后台检查计划的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 计划时间过去超过该秒数仍未执行，视为错过（通常是电脑休眠或应用未运行）
+const MISSED_TOLERANCE: i64 = 2 * 60;
+
+/// 日志最多保留的条数
+const MAX_LOG_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleMode {
+    /// 每隔固定小时数执行一次
+    Interval { hours: u32 },
+    /// 每周固定时间执行，weekday 为 0 表示周一
+    Weekly { weekday: u32, hour: u32, minute: u32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    pub enabled: bool,
+    pub mode: ScheduleMode,
+    /// 为空时清理全部收藏夹
+    #[serde(default)]
+    pub folder_ids: Vec<i64>,
+    /// 错过的计划在恢复后是否补执行一次（多次错过只补一次）
+    #[serde(default)]
+    pub run_missed: bool,
+    #[serde(default)]
+    pub last_run: Option<i64>,
+    /// 检查新失效视频的间隔小时数，为空时不单独检查（定时清理前仍会检查）
+    #[serde(default)]
+    pub watch_interval_hours: Option<u32>,
+    #[serde(default)]
+    pub last_watch: Option<i64>,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: ScheduleMode::Weekly { weekday: 0, hour: 9, minute: 0 },
+            folder_ids: Vec::new(),
+            run_missed: true,
+            last_run: None,
+            watch_interval_hours: None,
+            last_watch: None,
+        }
+    }
+}
+
+impl ScheduleConfig {
+    /// 检查计划时间是否有效，无效的时间会导致计划永远不执行
+    pub fn validate(&self) -> Result<(), String> {
+        match self.mode {
+            ScheduleMode::Interval { hours: 0 } => Err("执行间隔至少为 1 小时".to_string()),
+            ScheduleMode::Weekly { weekday, .. } if weekday > 6 => Err(format!("星期 {} 无效，应为 0（周一）到 6（周日）", weekday)),
+            ScheduleMode::Weekly { hour, minute, .. } if hour > 23 || minute > 59 => Err(format!("时间 {:02}:{:02} 无效", hour, minute)),
+            _ => Ok(()),
+        }
+    }
+
+    /// 不晚于 `now` 的最近一次计划执行时间
+    pub fn latest_slot(&self, now: i64) -> Option<i64> {
+        match self.mode {
+            ScheduleMode::Interval { hours } => {
+                let interval = hours.max(1) as i64 * 3600;
+                match self.last_run {
+                    Some(last_run) if now - last_run < interval => Some(last_run),
+                    Some(last_run) => Some(last_run + (now - last_run) / interval * interval),
+                    None => Some(now),
+                }
+            }
+            ScheduleMode::Weekly { weekday, hour, minute } => {
+                let now_local = Local.timestamp_opt(now, 0).single()?;
+                let days_back = (now_local.weekday().num_days_from_monday() + 7 - weekday % 7) % 7;
+                let date = now_local.date_naive() - chrono::Duration::days(days_back as i64);
+                let slot = Local.from_local_datetime(&date.and_hms_opt(hour, minute, 0)?).earliest()?.timestamp();
+                Some(if slot > now { slot - 7 * 24 * 3600 } else { slot })
+            }
+        }
+    }
+
+    /// 是否需要检查新失效的视频
+    pub fn watch_due(&self, now: i64) -> bool {
+        match (self.watch_interval_hours, self.last_watch) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(hours), Some(last_watch)) => now - last_watch >= hours.max(1) as i64 * 3600,
+        }
+    }
+
+    /// 当前需要执行的计划时间，未到期时返回 None
+    pub fn due_slot(&self, now: i64) -> Option<i64> {
+        let slot = self.latest_slot(now)?;
+        match self.last_run {
+            Some(last_run) if last_run >= slot => None,
+            _ => Some(slot),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleLogEntry {
+    pub scheduled_at: i64,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub missed: bool,
+    pub skipped: bool,
+    pub summary: Option<CleanSummary>,
+    pub error: Option<String>,
+}
+
+pub fn get_schedule_path() -> PathBuf {
+    get_config_dir().join("schedule.json")
+}
+
+pub fn get_schedule_log_path() -> PathBuf {
+    get_config_dir().join("schedule-log.json")
+}
+
+pub fn load_schedule() -> Result<ScheduleConfig, String> {
+    let path = get_schedule_path();
+
+    if !path.exists() {
+        return Ok(ScheduleConfig::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("读取计划配置失败: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析计划配置失败: {}", e))
+}
+
+pub fn save_schedule(schedule: &ScheduleConfig) -> Result<(), String> {
+    fs::create_dir_all(get_config_dir())
+        .map_err(|e| format!("创建配置目录失败: {}", e))?;
+
+    let content = serde_json::to_string_pretty(schedule)
+        .map_err(|e| format!("序列化计划配置失败: {}", e))?;
+
+    fs::write(get_schedule_path(), content)
+        .map_err(|e| format!("写入计划配置失败: {}", e))
+}
+
+pub fn load_schedule_log() -> Result<Vec<ScheduleLogEntry>, String> {
+    let path = get_schedule_log_path();
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("读取计划日志失败: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析计划日志失败: {}", e))
+}
+
+fn append_schedule_log(entry: ScheduleLogEntry) -> Result<(), String> {
+    let mut entries = load_schedule_log().unwrap_or_default();
+    entries.push(entry);
+    if entries.len() > MAX_LOG_ENTRIES {
+        entries.drain(..entries.len() - MAX_LOG_ENTRIES);
+    }
+
+    let content = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("序列化计划日志失败: {}", e))?;
+
+    fs::write(get_schedule_log_path(), content)
+        .map_err(|e| format!("写入计划日志失败: {}", e))
+}
+
+/// 检查新失效的视频，发现时发送通知和 `invalidated-videos-detected` 事件
+async fn check_invalidated(app: &AppHandle, client: &BilibiliClient, config: &AppConfig) -> Result<Vec<InvalidatedVideo>, String> {
+    // 获取失败的收藏夹保留原有基准，下次检查时仍能发现其中新失效的视频
+    let videos = invalidation::detect_invalidated(client, config).await?.videos;
+    if videos.is_empty() {
+        return Ok(videos);
+    }
+
+    let titles: Vec<&str> = videos.iter().take(3).map(|v| v.title.as_str()).collect();
+    let body = if videos.len() > titles.len() {
+        format!("{} 等 {} 个视频", titles.join("、"), videos.len())
+    } else {
+        titles.join("、")
+    };
+    let _ = app.notification().builder().title("收藏的视频已失效").body(body).show();
+    let _ = app.emit("invalidated-videos-detected", videos.clone());
+    Ok(videos)
+}
+
+/// 先检查并提醒新失效的视频，再执行清理，避免清理后无法得知失效了哪些视频
+async fn run_clean(app: &AppHandle, folder_ids: Vec<i64>) -> Result<CleanSummary, String> {
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    check_invalidated(app, &client, &config).await?;
+
+    let media_ids = if folder_ids.is_empty() {
+        client.get_favorite_folders(&config).await?.into_iter().map(|f| f.id).collect()
+    } else {
+        folder_ids
+    };
+
+    Ok(clean_folders(&client, &config, media_ids).await.summary)
+}
+
+async fn tick_watch(app: &AppHandle) -> Result<(), String> {
+    let mut schedule = load_schedule()?;
+    let now = Local::now().timestamp();
+    if !schedule.watch_due(now) {
+        return Ok(());
+    }
+
+    schedule.last_watch = Some(now);
+    save_schedule(&schedule)?;
+
+    let config = load_config()?.ok_or_else(|| "请先配置Cookie".to_string())?;
+    let client = BilibiliClient::from_config(&config)?;
+    check_invalidated(app, &client, &config).await.map(|_| ())
+}
+
+async fn tick(app: &AppHandle) -> Result<(), String> {
+    let mut schedule = load_schedule()?;
+    if !schedule.enabled {
+        return Ok(());
+    }
+
+    let now = Local::now().timestamp();
+    let Some(scheduled_at) = schedule.due_slot(now) else {
+        return Ok(());
+    };
+    let missed = now - scheduled_at > MISSED_TOLERANCE;
+
+    // 先记录执行时间，避免清理耗时较长时被重复触发
+    schedule.last_run = Some(now);
+    save_schedule(&schedule)?;
+
+    let skipped = missed && !schedule.run_missed;
+    let outcome = if skipped {
+        Ok(None)
+    } else {
+        run_clean(app, schedule.folder_ids.clone()).await.map(Some)
+    };
+
+    let entry = ScheduleLogEntry {
+        scheduled_at,
+        started_at: now,
+        finished_at: Local::now().timestamp(),
+        missed,
+        skipped,
+        summary: outcome.as_ref().ok().cloned().flatten(),
+        error: outcome.err(),
+    };
+
+    if !skipped {
+        let body = match (&entry.summary, &entry.error) {
+            (Some(summary), _) => format!("清理了 {} 个收藏夹，共移除 {} 个失效内容", summary.success, summary.total_cleaned),
+            (None, Some(error)) => format!("清理失败: {}", error),
+            (None, None) => String::new(),
+        };
+        let _ = app.notification().builder().title("定时清理完成").body(body).show();
+        let _ = app.emit("scheduled-clean-finished", entry.clone());
+    }
+
+    append_schedule_log(entry)
+}
+
+/// 将计划任务本身的错误（如读取配置失败）记入计划日志
+fn log_failure(error: String) {
+    let now = Local::now().timestamp();
+    let _ = append_schedule_log(ScheduleLogEntry {
+        scheduled_at: now,
+        started_at: now,
+        finished_at: now,
+        missed: false,
+        skipped: false,
+        summary: None,
+        error: Some(error),
+    });
+}
+
+/// 启动后台计划任务
+///
+/// 每次检查都比较墙上时间，因此休眠唤醒后的第一次检查即可发现错过的计划。
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(error) = tick_watch(&app).await {
+                log_failure(format!("检查失效视频失败: {}", error));
+            }
+            if let Err(error) = tick(&app).await {
+                log_failure(format!("定时清理失败: {}", error));
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3600;
+
+    fn schedule(mode: ScheduleMode, last_run: Option<i64>) -> ScheduleConfig {
+        ScheduleConfig { mode, last_run, ..Default::default() }
+    }
+
+    fn local(day: u32, hour: u32, minute: u32) -> i64 {
+        // 2024-01-03 为周三
+        Local.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap().timestamp()
+    }
+
+    #[test]
+    fn interval_slots() {
+        let now = 1_700_000_000;
+        let every = |hours: u32, last_run: Option<i64>| schedule(ScheduleMode::Interval { hours }, last_run);
+
+        // 从未执行过时立即执行
+        assert_eq!(every(6, None).latest_slot(now), Some(now));
+        assert_eq!(every(6, None).due_slot(now), Some(now));
+
+        // 间隔内不再执行
+        let recent = every(6, Some(now - 2 * HOUR));
+        assert_eq!(recent.latest_slot(now), Some(now - 2 * HOUR));
+        assert_eq!(recent.due_slot(now), None);
+
+        // 错过多次时只返回最近一次
+        let last_run = now - 20 * HOUR - 30 * 60;
+        let missed = every(6, Some(last_run));
+        assert_eq!(missed.latest_slot(now), Some(last_run + 18 * HOUR));
+        assert_eq!(missed.due_slot(now), Some(last_run + 18 * HOUR));
+        assert_eq!(every(6, Some(last_run + 18 * HOUR)).due_slot(now), None);
+    }
+
+    #[test]
+    fn weekly_slots() {
+        let now = local(3, 12, 0);
+        let weekly = |weekday: u32, hour: u32, last_run: Option<i64>| schedule(ScheduleMode::Weekly { weekday, hour, minute: 30 }, last_run);
+
+        // 当天计划时间已过
+        assert_eq!(weekly(2, 9, None).latest_slot(now), Some(local(3, 9, 30)));
+        // 当天计划时间未到，取上周
+        assert_eq!(weekly(2, 15, None).latest_slot(now), Some(local(3, 15, 30) - 7 * 24 * HOUR));
+        // 本周一
+        assert_eq!(weekly(0, 9, None).latest_slot(now), Some(local(1, 9, 30)));
+        // 上周日
+        assert_eq!(weekly(6, 9, None).latest_slot(now), Some(local(3, 9, 30) - 3 * 24 * HOUR));
+
+        assert_eq!(weekly(2, 9, None).due_slot(now), Some(local(3, 9, 30)));
+        assert_eq!(weekly(2, 9, Some(local(3, 9, 30))).due_slot(now), None);
+        assert_eq!(weekly(2, 9, Some(local(3, 10, 0))).due_slot(now), None);
+        assert_eq!(weekly(2, 9, Some(local(1, 9, 30))).due_slot(now), Some(local(3, 9, 30)));
+    }
+
+    #[test]
+    fn validates_schedule() {
+        let weekly = |weekday: u32, hour: u32, minute: u32| schedule(ScheduleMode::Weekly { weekday, hour, minute }, None).validate();
+        assert!(schedule(ScheduleMode::Interval { hours: 0 }, None).validate().is_err());
+        assert!(schedule(ScheduleMode::Interval { hours: 1 }, None).validate().is_ok());
+        assert!(weekly(7, 9, 0).is_err());
+        assert!(weekly(6, 9, 0).is_ok());
+        assert!(weekly(0, 24, 0).is_err());
+        assert!(weekly(0, 23, 60).is_err());
+        assert!(weekly(0, 23, 59).is_ok());
+    }
+}
@@ -0,0 +1,434 @@
+//! 收藏内容的本地全文索引，覆盖标题、简介和UP主名称
+//!
+//! 中日韩文字没有空格分词，按单字和相邻两字建立索引；其他文字按字母数字连续片段分词，
+//! 查询的最后一个词支持前缀匹配。索引与收藏夹缓存存放在同一目录下，随扫描和修改操作增量更新。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use crate::bilibili::{FavoriteFolder, FavoriteResource, ResourceId};
+
+/// 未指定数量时最多返回的结果数
+pub const DEFAULT_LIMIT: usize = 200;
+
+/// 字段在命中位掩码中的标记
+const FIELD_TITLE: u8 = 1;
+const FIELD_UPPER: u8 = 2;
+const FIELD_INTRO: u8 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub folder_id: i64,
+    pub folder_title: String,
+    pub resource: FavoriteResource,
+    /// 命中的字段：title、upper、intro
+    pub matched_fields: Vec<String>,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDoc {
+    folder_id: i64,
+    resource: FavoriteResource,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexData {
+    next_doc: u64,
+    folder_titles: BTreeMap<i64, String>,
+    docs: BTreeMap<u64, IndexedDoc>,
+    /// 词 -> (文档 -> 命中字段的位掩码)
+    postings: BTreeMap<String, BTreeMap<u64, u8>>,
+}
+
+pub struct SearchIndex {
+    path: PathBuf,
+    data: IndexData,
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF      // 平假名、片假名
+        | 0x3400..=0x4DBF    // 扩展A
+        | 0x4E00..=0x9FFF    // 基本汉字
+        | 0xAC00..=0xD7AF    // 韩文音节
+        | 0xF900..=0xFAFF    // 兼容汉字
+        | 0x20000..=0x2FA1F  // 扩展B及以后
+    )
+}
+
+/// 将全角字母数字转为半角，便于与半角输入互相匹配
+fn normalize(c: char) -> char {
+    match c as u32 {
+        0xFF01..=0xFF5E => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// 文本片段：连续的中日韩文字或连续的字母数字
+enum Segment {
+    Cjk(Vec<char>),
+    Word(String),
+}
+
+fn segments(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut cjk = Vec::new();
+    let mut word = String::new();
+
+    for c in text.chars().map(normalize).flat_map(char::to_lowercase) {
+        if is_cjk(c) {
+            if !word.is_empty() {
+                segments.push(Segment::Word(std::mem::take(&mut word)));
+            }
+            cjk.push(c);
+        } else if c.is_alphanumeric() {
+            if !cjk.is_empty() {
+                segments.push(Segment::Cjk(std::mem::take(&mut cjk)));
+            }
+            word.push(c);
+        } else {
+            if !cjk.is_empty() {
+                segments.push(Segment::Cjk(std::mem::take(&mut cjk)));
+            }
+            if !word.is_empty() {
+                segments.push(Segment::Word(std::mem::take(&mut word)));
+            }
+        }
+    }
+    if !cjk.is_empty() {
+        segments.push(Segment::Cjk(cjk));
+    }
+    if !word.is_empty() {
+        segments.push(Segment::Word(word));
+    }
+    segments
+}
+
+/// 建立索引时的分词：中日韩文字生成单字和两字词，其余按词
+fn index_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for segment in segments(text) {
+        match segment {
+            Segment::Cjk(chars) => {
+                tokens.extend(chars.iter().map(|c| c.to_string()));
+                tokens.extend(chars.windows(2).map(|pair| pair.iter().collect()));
+            }
+            Segment::Word(word) => tokens.push(word),
+        }
+    }
+    tokens
+}
+
+/// 查询词：中日韩片段拆成两字词（单字片段保留单字），其余按词，最后一个词允许前缀匹配
+struct QueryTerm {
+    token: String,
+    prefix: bool,
+}
+
+fn query_terms(query: &str) -> (Vec<QueryTerm>, Vec<String>) {
+    let segments = segments(query);
+    let mut terms = Vec::new();
+    // 多字的中日韩片段需要在原文中连续出现，两字词命中后再用原文校验
+    let mut phrases = Vec::new();
+    let last = segments.len().saturating_sub(1);
+
+    for (index, segment) in segments.into_iter().enumerate() {
+        match segment {
+            Segment::Cjk(chars) if chars.len() == 1 => {
+                terms.push(QueryTerm { token: chars[0].to_string(), prefix: false });
+            }
+            Segment::Cjk(chars) => {
+                terms.extend(chars.windows(2).map(|pair| QueryTerm { token: pair.iter().collect(), prefix: false }));
+                if chars.len() > 2 {
+                    phrases.push(chars.into_iter().collect());
+                }
+            }
+            Segment::Word(word) => terms.push(QueryTerm { token: word, prefix: index == last }),
+        }
+    }
+    (terms, phrases)
+}
+
+fn fields_of(resource: &FavoriteResource) -> [(u8, &str); 3] {
+    [
+        (FIELD_TITLE, resource.title.as_str()),
+        (FIELD_UPPER, resource.upper.as_ref().map(|u| u.name.as_str()).unwrap_or_default()),
+        (FIELD_INTRO, resource.intro.as_str()),
+    ]
+}
+
+fn field_weight(mask: u8) -> u32 {
+    let mut weight = 0;
+    if mask & FIELD_TITLE != 0 { weight += 5; }
+    if mask & FIELD_UPPER != 0 { weight += 3; }
+    if mask & FIELD_INTRO != 0 { weight += 1; }
+    weight
+}
+
+fn field_names(mask: u8) -> Vec<String> {
+    [(FIELD_TITLE, "title"), (FIELD_UPPER, "upper"), (FIELD_INTRO, "intro")].into_iter()
+        .filter(|(field, _)| mask & field != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn same_resource(resource: &FavoriteResource, id: &ResourceId) -> bool {
+    resource.id == id.id && resource.resource_type == id.resource_type
+}
+
+impl SearchIndex {
+    /// 读取索引文件，不存在或损坏时返回空索引
+    pub fn open(path: PathBuf) -> Self {
+        let data = fs::read_to_string(&path).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, data }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// 写入失败时删除旧的索引文件，下次打开时根据缓存重新生成，避免使用过时的索引
+    pub fn save(&self) -> Result<(), String> {
+        let result = self.path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| format!("创建缓存目录失败: {}", e))
+            .and_then(|_| serde_json::to_string(&self.data).map_err(|e| format!("序列化搜索索引失败: {}", e)))
+            .and_then(|content| fs::write(&self.path, content).map_err(|e| format!("写入搜索索引失败: {}", e)));
+
+        if result.is_err() {
+            let _ = fs::remove_file(&self.path);
+        }
+        result
+    }
+
+    fn add(&mut self, folder_id: i64, resource: FavoriteResource) {
+        let doc_id = self.data.next_doc;
+        self.data.next_doc += 1;
+
+        for (field, text) in fields_of(&resource) {
+            for token in index_tokens(text) {
+                *self.data.postings.entry(token).or_default().entry(doc_id).or_default() |= field;
+            }
+        }
+        self.data.docs.insert(doc_id, IndexedDoc { folder_id, resource });
+    }
+
+    fn remove_where(&mut self, predicate: impl Fn(&IndexedDoc) -> bool) {
+        let removed: Vec<u64> = self.data.docs.iter()
+            .filter(|(_, doc)| predicate(doc))
+            .map(|(doc_id, _)| *doc_id)
+            .collect();
+
+        for doc_id in removed {
+            let Some(doc) = self.data.docs.remove(&doc_id) else { continue };
+            for (_, text) in fields_of(&doc.resource) {
+                for token in index_tokens(text) {
+                    if let Some(posting) = self.data.postings.get_mut(&token) {
+                        posting.remove(&doc_id);
+                        if posting.is_empty() {
+                            self.data.postings.remove(&token);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 用收藏夹的最新内容替换索引中该收藏夹的全部内容
+    pub fn update_folder(&mut self, folder: &FavoriteFolder, resources: &[FavoriteResource]) {
+        self.remove_where(|doc| doc.folder_id == folder.id);
+        self.data.folder_titles.insert(folder.id, folder.title.clone());
+        for resource in resources {
+            self.add(folder.id, resource.clone());
+        }
+    }
+
+    /// 索引中该收藏夹的内容，即最后一次扫描或修改后已知的状态
+    pub fn folder_resources(&self, folder_id: i64) -> Vec<&FavoriteResource> {
+        self.data.docs.values()
+            .filter(|doc| doc.folder_id == folder_id)
+            .map(|doc| &doc.resource)
+            .collect()
+    }
+
+    /// 同步收藏夹列表：更新标题，移除已删除收藏夹的内容
+    pub fn sync_folders(&mut self, folders: &[FavoriteFolder]) {
+        let titles: HashMap<i64, &str> = folders.iter().map(|f| (f.id, f.title.as_str())).collect();
+        self.remove_where(|doc| !titles.contains_key(&doc.folder_id));
+        self.data.folder_titles = folders.iter().map(|f| (f.id, f.title.clone())).collect();
+    }
+
+    /// 从收藏夹中删除内容后调用
+    pub fn remove_resources(&mut self, folder_id: i64, resources: &[ResourceId]) {
+        self.remove_where(|doc| doc.folder_id == folder_id && resources.iter().any(|id| same_resource(&doc.resource, id)));
+    }
+
+    /// 清理收藏夹中的失效内容后调用
+    pub fn remove_invalid(&mut self, folder_id: i64) {
+        self.remove_where(|doc| doc.folder_id == folder_id && doc.resource.is_invalid());
+    }
+
+    /// 复制内容到目标收藏夹后调用，目标收藏夹中已有的内容不重复添加
+    pub fn copy_resources(&mut self, source_id: i64, target_id: i64, resources: &[ResourceId]) {
+        let copies: Vec<FavoriteResource> = self.data.docs.values()
+            .filter(|doc| doc.folder_id == source_id && resources.iter().any(|id| same_resource(&doc.resource, id)))
+            .filter(|doc| !self.data.docs.values().any(|d| d.folder_id == target_id && d.resource.id == doc.resource.id && d.resource.resource_type == doc.resource.resource_type))
+            .map(|doc| doc.resource.clone())
+            .collect();
+
+        for resource in copies {
+            self.add(target_id, resource);
+        }
+    }
+
+    /// 移动内容到目标收藏夹后调用
+    pub fn move_resources(&mut self, source_id: i64, target_id: i64, resources: &[ResourceId]) {
+        self.copy_resources(source_id, target_id, resources);
+        self.remove_resources(source_id, resources);
+    }
+
+    fn candidates(&self, term: &QueryTerm) -> BTreeMap<u64, u8> {
+        if !term.prefix {
+            return self.data.postings.get(&term.token).cloned().unwrap_or_default();
+        }
+
+        let mut merged: BTreeMap<u64, u8> = BTreeMap::new();
+        for (_, posting) in self.data.postings.range(term.token.clone()..)
+            .take_while(|(token, _)| token.starts_with(&term.token))
+        {
+            for (doc_id, mask) in posting {
+                *merged.entry(*doc_id).or_default() |= mask;
+            }
+        }
+        merged
+    }
+
+    /// 搜索全部词都命中的内容，按相关度和收藏时间排序
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let (terms, phrases) = query_terms(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matched: Option<BTreeMap<u64, (u8, u32)>> = None;
+        for term in &terms {
+            let candidates = self.candidates(term);
+            matched = Some(match matched {
+                None => candidates.into_iter().map(|(doc_id, mask)| (doc_id, (mask, field_weight(mask)))).collect(),
+                Some(previous) => previous.into_iter()
+                    .filter_map(|(doc_id, (fields, score))| {
+                        candidates.get(&doc_id).map(|mask| (doc_id, (fields | mask, score + field_weight(*mask))))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<SearchHit> = matched.unwrap_or_default().into_iter()
+            .filter_map(|(doc_id, (fields, score))| self.data.docs.get(&doc_id).map(|doc| (doc, fields, score)))
+            .filter(|(doc, _, _)| {
+                phrases.iter().all(|phrase: &String| {
+                    fields_of(&doc.resource).iter().any(|(_, text)| text.to_lowercase().contains(phrase.as_str()))
+                })
+            })
+            .map(|(doc, fields, score)| SearchHit {
+                folder_id: doc.folder_id,
+                folder_title: self.data.folder_titles.get(&doc.folder_id).cloned().unwrap_or_default(),
+                resource: doc.resource.clone(),
+                matched_fields: field_names(fields),
+                score,
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then(b.resource.fav_time.cmp(&a.resource.fav_time)));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(id: i64, title: &str, upper: &str, intro: &str) -> FavoriteResource {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": 2,
+            "title": title,
+            "intro": intro,
+            "duration": 60,
+            "attr": 0,
+            "ctime": id,
+            "pubtime": id,
+            "fav_time": id,
+            "upper": { "mid": id, "name": upper }
+        })).unwrap()
+    }
+
+    fn index() -> SearchIndex {
+        let folder: FavoriteFolder = serde_json::from_value(serde_json::json!({
+            "id": 1, "fid": 1, "mid": 1, "title": "收藏夹", "attr": 0, "fav_state": 0, "media_count": 4
+        })).unwrap();
+        let mut index = SearchIndex::open(PathBuf::from("/nonexistent/search-index.json"));
+        index.update_folder(&folder, &[
+            resource(1, "机器学习入门教程", "老师A", ""),
+            resource(2, "学习机器的使用方法", "UP主", ""),
+            resource(3, "Rust Programming", "ＦｅｒｒｉｓＣａｔ", "异步编程"),
+            resource(4, "日常vlog", "学习区", "机器"),
+        ]);
+        index
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<i64> {
+        let mut ids: Vec<i64> = hits.iter().map(|h| h.resource.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn splits_cjk_into_unigrams_and_bigrams() {
+        assert_eq!(index_tokens("机器学习"), vec!["机", "器", "学", "习", "机器", "器学", "学习"]);
+        assert_eq!(index_tokens("Rust入门2024"), vec!["rust", "入", "门", "入门", "2024"]);
+        assert_eq!(index_tokens("ＡＢＣ"), vec!["abc"]);
+    }
+
+    #[test]
+    fn builds_query_terms_and_phrases() {
+        let (terms, phrases) = query_terms("机器学习 ru");
+        let tokens: Vec<(&str, bool)> = terms.iter().map(|t| (t.token.as_str(), t.prefix)).collect();
+        assert_eq!(tokens, vec![("机器", false), ("器学", false), ("学习", false), ("ru", true)]);
+        assert_eq!(phrases, vec!["机器学习".to_string()]);
+
+        let (terms, phrases) = query_terms("学");
+        assert_eq!(terms.len(), 1);
+        assert!(phrases.is_empty());
+    }
+
+    #[test]
+    fn matches_cjk_phrases_in_order() {
+        let index = index();
+        // 两字词都出现，但只有 1 中“机器学习”连续出现
+        assert_eq!(ids(&index.search("机器学习", 10)), vec![1]);
+        assert_eq!(ids(&index.search("机器", 10)), vec![1, 2, 4]);
+        assert_eq!(ids(&index.search("学", 10)), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn matches_words_by_prefix_and_reports_fields() {
+        let index = index();
+        assert_eq!(ids(&index.search("prog", 10)), vec![3]);
+        assert_eq!(ids(&index.search("rust prog", 10)), vec![3]);
+        assert!(index.search("prog rust2", 10).is_empty());
+        assert_eq!(ids(&index.search("ferris", 10)), vec![3]);
+
+        let hits = index.search("机器", 10);
+        let title_hit = hits.iter().find(|h| h.resource.id == 1).unwrap();
+        let intro_hit = hits.iter().find(|h| h.resource.id == 4).unwrap();
+        assert_eq!(title_hit.matched_fields, vec!["title"]);
+        assert_eq!(intro_hit.matched_fields, vec!["intro"]);
+        assert!(title_hit.score > intro_hit.score);
+    }
+}
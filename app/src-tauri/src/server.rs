@@ -0,0 +1,461 @@
+//! 本地 HTTP 接口服务，提供与 `new/backend`（Express）相同的路由和响应格式
+
+use axum::extract::{Path, Query};
+use axum::http::{HeaderValue, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use crate::bilibili::{BilibiliClient, ResourceId, MAX_FAVORITES_LIMIT};
+use crate::cache::FavoritesCache;
+use crate::cookies::{self, CookieFormat};
+use crate::config::{delete_config, load_config, save_login_config, AppConfig};
+
+type ApiResponse = (StatusCode, Json<Value>);
+
+fn success(data: Value) -> ApiResponse {
+    (StatusCode::OK, Json(json!({ "success": true, "data": data })))
+}
+
+fn success_message(message: &str) -> ApiResponse {
+    (StatusCode::OK, Json(json!({ "success": true, "message": message })))
+}
+
+fn failure(status: StatusCode, error: impl Into<String>) -> ApiResponse {
+    (status, Json(json!({ "success": false, "error": error.into() })))
+}
+
+fn bad_request(error: impl Into<String>) -> ApiResponse {
+    failure(StatusCode::BAD_REQUEST, error)
+}
+
+fn server_error(error: impl Into<String>) -> ApiResponse {
+    failure(StatusCode::INTERNAL_SERVER_ERROR, error)
+}
+
+/// 读取已保存的配置，未配置 Cookie 时返回 400 响应
+fn require_config() -> Result<AppConfig, ApiResponse> {
+    match load_config() {
+        Ok(Some(config)) if !config.cookie.is_empty() => Ok(config),
+        Ok(_) => Err(bad_request("请先配置Cookie")),
+        Err(error) => Err(server_error(error)),
+    }
+}
+
+/// 读取配置并按其中的网络设置创建客户端
+fn require_client() -> Result<(AppConfig, BilibiliClient), ApiResponse> {
+    let config = require_config()?;
+    let client = BilibiliClient::from_config(&config).map_err(server_error)?;
+    Ok((config, client))
+}
+
+/// 前端既可能传数字也可能传字符串形式的ID
+fn parse_id(value: Option<&Value>) -> Option<i64> {
+    match value? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn folder_info_json(info: &crate::bilibili::FavoriteFolder) -> Value {
+    json!({
+        "mediaId": info.id,
+        "title": info.title,
+        "mediaCount": info.media_count,
+        "upper": { "mid": info.mid },
+    })
+}
+
+// 配置接口
+/// Cookie 和 csrf_token 可直接用于登录，不通过接口返回，只返回是否已配置
+async fn get_config_handler() -> ApiResponse {
+    match load_config() {
+        Ok(config) => {
+            let mut config = config.unwrap_or_default();
+            config.network.proxy = config.network.masked_proxy();
+            let has_cookie = !config.cookie.is_empty();
+            let mut data = json!(config);
+            if let Some(fields) = data.as_object_mut() {
+                fields.remove("cookie");
+                fields.remove("csrf_token");
+                fields.insert("hasCookie".to_string(), json!(has_cookie));
+            }
+            success(data)
+        }
+        Err(_) => server_error("获取配置失败"),
+    }
+}
+
+async fn save_config_handler(Json(body): Json<Value>) -> ApiResponse {
+    let field = |name: &str| body.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let mut config = AppConfig {
+        up_mid: field("up_mid"),
+        csrf_token: field("csrf_token"),
+        cookie: field("cookie"),
+        ..Default::default()
+    };
+
+    if config.up_mid.is_empty() || config.csrf_token.is_empty() || config.cookie.is_empty() {
+        return bad_request("配置缺少必要字段");
+    }
+
+    match save_login_config(&mut config) {
+        Ok(_) => success_message("配置保存成功"),
+        Err(_) => server_error("保存配置失败"),
+    }
+}
+
+async fn delete_config_handler() -> ApiResponse {
+    match delete_config() {
+        Ok(_) => success_message("配置删除成功"),
+        Err(_) => server_error("删除配置失败"),
+    }
+}
+
+// 收藏夹接口
+async fn favorites_handler() -> ApiResponse {
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    match client.get_favorite_folders(&config).await {
+        Ok(folders) => success(json!(folders)),
+        Err(error) => server_error(error),
+    }
+}
+
+async fn favorite_details_handler(Path(id): Path<i64>) -> ApiResponse {
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    match client.get_favorite_resources(id, &config).await {
+        Ok(details) => success(json!(details)),
+        Err(error) => server_error(error),
+    }
+}
+
+// 清理接口
+async fn clean_handler(Json(body): Json<Value>) -> ApiResponse {
+    let Some(media_id) = parse_id(body.get("mediaId")) else {
+        return bad_request("缺少收藏夹ID");
+    };
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    let outcome = client.clean_favorite_folder(media_id, &config).await;
+    let cache = FavoritesCache::new(&config.up_mid);
+    cache.invalidate(&[media_id]);
+    if outcome.is_ok() {
+        let _ = cache.update_index(|index| index.remove_invalid(media_id));
+    }
+    match outcome {
+        Ok(cleaned_count) => success(json!({ "mediaId": media_id, "cleanedCount": cleaned_count })),
+        Err(error) => server_error(error),
+    }
+}
+
+async fn clean_batch_handler(Json(body): Json<Value>) -> ApiResponse {
+    let media_ids: Vec<i64> = match body.get("mediaIds").and_then(|v| v.as_array()) {
+        Some(ids) if !ids.is_empty() => ids.iter().filter_map(|id| parse_id(Some(id))).collect(),
+        _ => return bad_request("请提供有效的收藏夹ID列表"),
+    };
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    let result = crate::clean_folders(&client, &config, media_ids).await;
+    let results: Vec<Value> = result.results.iter().map(|r| json!({
+        "mediaId": r.media_id,
+        "success": r.success,
+        "cleanedCount": r.cleaned_count,
+        "error": r.error,
+    })).collect();
+
+    success(json!({
+        "results": results,
+        "summary": {
+            "total": result.summary.total,
+            "success": result.summary.success,
+            "failure": result.summary.failure,
+            "totalCleaned": result.summary.total_cleaned,
+        }
+    }))
+}
+
+// Cookie 转换接口
+async fn convert_cookies_handler(Json(body): Json<Value>) -> ApiResponse {
+    let parsed = match body.get("cookies") {
+        Some(Value::String(header)) => cookies::parse_cookies(header, Some(CookieFormat::Header)),
+        Some(items @ Value::Array(_)) => cookies::parse_cookies(&items.to_string(), Some(CookieFormat::JsonArray)),
+        _ => return bad_request("请提供Cookie数据"),
+    };
+
+    match parsed {
+        Ok(parsed) => success(cookies::to_tinydb(&parsed)),
+        Err(error) => bad_request(error),
+    }
+}
+
+// 移动接口
+async fn move_info_handler(Path(media_id): Path<i64>) -> ApiResponse {
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    match client.get_favorite_resources(media_id, &config).await {
+        Ok(details) => success(folder_info_json(&details.info)),
+        Err(error) => server_error(error),
+    }
+}
+
+async fn move_calculate_handler(Query(query): Query<HashMap<String, String>>) -> ApiResponse {
+    let (Some(src_media_id), Some(tar_media_id)) = (
+        query.get("srcMediaId").and_then(|v| v.parse::<i64>().ok()),
+        query.get("tarMediaId").and_then(|v| v.parse::<i64>().ok()),
+    ) else {
+        return bad_request("缺少源收藏夹ID或目标收藏夹ID");
+    };
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    let (src, tar) = tokio::join!(
+        client.get_favorite_resources(src_media_id, &config),
+        client.get_favorite_resources(tar_media_id, &config),
+    );
+    let (src, tar) = match (src, tar) {
+        (Ok(src), Ok(tar)) => (src.info, tar.info),
+        (Err(error), _) | (_, Err(error)) => return server_error(error),
+    };
+
+    let movable_count = std::cmp::min(tar.available_space(), src.media_count);
+    success(json!({
+        "srcFolder": folder_info_json(&src),
+        "tarFolder": folder_info_json(&tar),
+        "movableCount": movable_count,
+        "canMove": movable_count > 0,
+    }))
+}
+
+#[derive(Deserialize)]
+struct VideosQuery {
+    count: Option<String>,
+}
+
+async fn move_videos_handler(Path(media_id): Path<i64>, Query(query): Query<VideosQuery>) -> ApiResponse {
+    let count = match query.count.and_then(|c| c.parse::<i32>().ok()) {
+        Some(count) if count > 0 => count,
+        _ => return bad_request("视频数量必须是正整数"),
+    };
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    match client.get_favorite_videos(media_id, count, &config).await {
+        Ok(videos) => success(json!({
+            "mediaId": media_id,
+            "actualCount": videos.len(),
+            "videos": videos,
+            "requestedCount": count,
+        })),
+        Err(error) => server_error(error),
+    }
+}
+
+async fn move_execute_handler(Json(body): Json<Value>) -> ApiResponse {
+    let (Some(src_media_id), Some(tar_media_id)) = (parse_id(body.get("srcMediaId")), parse_id(body.get("tarMediaId"))) else {
+        return bad_request("缺少源收藏夹ID或目标收藏夹ID");
+    };
+    if src_media_id == tar_media_id {
+        return bad_request("源收藏夹和目标收藏夹不能相同");
+    }
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    let (src, tar) = match (
+        client.get_favorite_resources(src_media_id, &config).await,
+        client.get_favorite_resources(tar_media_id, &config).await,
+    ) {
+        (Ok(src), Ok(tar)) => (src.info, tar.info),
+        (Err(error), _) | (_, Err(error)) => return server_error(error),
+    };
+
+    let movable_count = std::cmp::min(tar.available_space(), src.media_count);
+    if movable_count == 0 {
+        let message = if tar.media_count >= MAX_FAVORITES_LIMIT {
+            format!("目标收藏夹已满（{}个视频上限）", MAX_FAVORITES_LIMIT)
+        } else {
+            "源收藏夹中没有视频可移动".to_string()
+        };
+        return success(json!({ "movedCount": 0, "message": message }));
+    }
+
+    // 与原后端一致：移动源收藏夹中最新收藏的视频
+    let videos = match client.get_favorite_videos(src_media_id, movable_count, &config).await {
+        Ok(videos) => videos,
+        Err(error) => return server_error(error),
+    };
+    let video_ids: Vec<i64> = videos.iter().map(|v| v.id).collect();
+
+    let outcome = client.move_selected_videos(&video_ids, src_media_id, tar_media_id, &config).await;
+    let cache = FavoritesCache::new(&config.up_mid);
+    cache.invalidate(&[src_media_id, tar_media_id]);
+    let result = match outcome {
+        Ok(result) if result.fail_count == 0 => result,
+        Ok(result) => return server_error(result.message.unwrap_or_default()),
+        Err(error) => return server_error(error),
+    };
+    let moved: Vec<ResourceId> = result.videos.iter().flatten()
+        .map(ResourceId::from)
+        .collect();
+    let _ = cache.update_index(|index| index.move_resources(src_media_id, tar_media_id, &moved));
+    let moved_count = result.moved_count;
+
+    success(json!({
+        "srcMediaId": src_media_id,
+        "tarMediaId": tar_media_id,
+        "srcFolder": {
+            "id": src.id,
+            "title": src.title,
+            "originalCount": src.media_count,
+            "remainingCount": src.media_count - moved_count,
+        },
+        "tarFolder": {
+            "id": tar.id,
+            "title": tar.title,
+            "originalCount": tar.media_count,
+            "newCount": tar.media_count + moved_count,
+        },
+        "movedCount": moved_count,
+        "movableCount": movable_count,
+        "videos": result.videos.unwrap_or_default(),
+    }))
+}
+
+// 排序接口
+async fn sort_folders_handler() -> ApiResponse {
+    let (config, client) = match require_client() {
+        Ok(pair) => pair,
+        Err(response) => return response,
+    };
+
+    match client.get_favorite_folders(&config).await {
+        Ok(folders) => success(json!(folders.iter().map(|f| json!({
+            "id": f.id,
+            "title": f.title,
+            "media_count": f.media_count,
+            "fid": f.fid,
+            "mid": f.mid,
+            "attr": f.attr,
+            "state": f.state,
+        })).collect::<Vec<_>>())),
+        Err(error) => server_error(error),
+    }
+}
+
+async fn sort_execute_handler(Json(body): Json<Value>) -> ApiResponse {
+    let folder_ids: Vec<i64> = match body.get("folderIds").and_then(|v| v.as_array()) {
+        Some(ids) if !ids.is_empty() => ids.iter().filter_map(|id| parse_id(Some(id))).collect(),
+        _ => return bad_request("请提供有效的收藏夹ID列表"),
+    };
+    if let Err(response) = require_config() {
+        return response;
+    }
+
+    match crate::execute_sort(folder_ids).await {
+        Ok(result) if result.success => success(json!({
+            "sortedCount": result.sorted_count,
+            "folderIds": result.folder_ids,
+            "message": result.message,
+        })),
+        Ok(result) => bad_request(result.message),
+        Err(error) => server_error(error),
+    }
+}
+
+async fn health_handler() -> Json<Value> {
+    Json(json!({ "status": "ok", "timestamp": chrono::Utc::now().to_rfc3339() }))
+}
+
+async fn not_found_handler() -> ApiResponse {
+    failure(StatusCode::NOT_FOUND, "API接口不存在")
+}
+
+/// 只允许本机页面跨域访问，避免其他网站读取或修改配置
+fn is_local_origin(origin: &HeaderValue) -> bool {
+    let Ok(origin) = origin.to_str() else {
+        return false;
+    };
+    let host = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let host = host.rsplit_once(':').filter(|(_, port)| port.chars().all(|c| c.is_ascii_digit())).map_or(host, |(host, _)| host);
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]" | "tauri.localhost")
+}
+
+fn cors_layer() -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin, _| is_local_origin(origin)))
+        .allow_methods(Any)
+        .allow_headers(Any)
+}
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/health", get(health_handler))
+        .route("/api/config", get(get_config_handler).post(save_config_handler).delete(delete_config_handler))
+        .route("/api/favorites", get(favorites_handler))
+        .route("/api/favorites/:id", get(favorite_details_handler))
+        .route("/api/clean", post(clean_handler))
+        .route("/api/clean/batch", post(clean_batch_handler))
+        .route("/api/convert-cookies", post(convert_cookies_handler))
+        .route("/api/move/info/:media_id", get(move_info_handler))
+        .route("/api/move/calculate", get(move_calculate_handler))
+        .route("/api/move/videos/:media_id", get(move_videos_handler))
+        .route("/api/move/execute", post(move_execute_handler))
+        .route("/api/sort/folders", get(sort_folders_handler))
+        .route("/api/sort/execute", post(sort_execute_handler))
+        .fallback(not_found_handler)
+        .layer(cors_layer())
+}
+
+/// 在指定地址启动服务，直到进程退出
+pub async fn serve(addr: &str) -> Result<(), String> {
+    let listener = tokio::net::TcpListener::bind(addr).await
+        .map_err(|e| format!("监听 {} 失败: {}", addr, e))?;
+
+    println!("🚀 服务器运行在 http://{}", addr);
+    println!("📊 健康检查: http://{}/health", addr);
+
+    axum::serve(listener, router()).await
+        .map_err(|e| format!("服务器错误: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_only_local_origins() {
+        let allowed = |origin: &str| is_local_origin(&HeaderValue::from_str(origin).unwrap());
+        assert!(allowed("http://localhost:5173"));
+        assert!(allowed("http://127.0.0.1:3000"));
+        assert!(allowed("tauri://localhost"));
+        assert!(allowed("http://[::1]:8080"));
+        assert!(!allowed("https://example.com"));
+        assert!(!allowed("http://localhost.example.com"));
+        assert!(!allowed("null"));
+    }
+}
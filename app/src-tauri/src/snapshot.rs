@@ -0,0 +1,323 @@
+//! 收藏夹快照，保存在 `~/.bilibili-fav-cleaner/snapshots/<up_mid>/<时间戳>.json`
+//!
+//! 快照只保存收藏夹信息和每个内容的ID、标题与状态，不保存封面、简介等字段，并使用紧凑的 JSON 格式。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use chrono::Local;
+use crate::bilibili::{FavoriteFolder, FavoriteResource, ResourceId, ResourceType};
+use crate::config::get_config_dir;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotResource {
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub resource_type: ResourceType,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub bvid: String,
+    pub title: String,
+    pub attr: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper: Option<String>,
+}
+
+impl SnapshotResource {
+    fn key(&self) -> ResourceId {
+        ResourceId { id: self.id, resource_type: self.resource_type }
+    }
+
+    /// 与 `FavoriteResource::is_invalid` 的判断一致
+    pub fn is_invalid(&self) -> bool {
+        self.attr & 1 == 1 || self.title == "已失效视频"
+    }
+}
+
+impl From<&FavoriteResource> for SnapshotResource {
+    fn from(resource: &FavoriteResource) -> Self {
+        Self {
+            id: resource.id,
+            resource_type: resource.resource_type,
+            bvid: resource.bvid.clone(),
+            title: resource.title.clone(),
+            attr: resource.attr,
+            upper: resource.upper.as_ref().map(|u| u.name.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFolder {
+    pub folder: FavoriteFolder,
+    pub resources: Vec<SnapshotResource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// 拍摄时间戳（秒），同时作为快照ID
+    pub taken_at: i64,
+    pub folders: Vec<SnapshotFolder>,
+}
+
+impl Snapshot {
+    pub fn new(folders: Vec<(FavoriteFolder, Vec<FavoriteResource>)>) -> Self {
+        Self {
+            taken_at: Local::now().timestamp(),
+            folders: folders.into_iter()
+                .map(|(folder, resources)| SnapshotFolder {
+                    folder,
+                    resources: resources.iter().map(SnapshotResource::from).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn summary(&self) -> SnapshotSummary {
+        SnapshotSummary {
+            id: self.taken_at,
+            folder_count: self.folders.len(),
+            resource_count: self.folders.iter().map(|f| f.resources.len()).sum(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSummary {
+    pub id: i64,
+    pub folder_count: usize,
+    pub resource_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffItem {
+    pub resource: SnapshotResource,
+    pub folder_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovedItem {
+    pub resource: SnapshotResource,
+    pub from_folder_ids: Vec<i64>,
+    pub to_folder_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetitledItem {
+    pub resource: SnapshotResource,
+    pub old_title: String,
+    pub folder_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub from: i64,
+    pub to: i64,
+    /// 两个快照中出现过的收藏夹标题，以较新的为准
+    pub folder_titles: BTreeMap<i64, String>,
+    pub added: Vec<DiffItem>,
+    pub removed: Vec<DiffItem>,
+    pub moved: Vec<MovedItem>,
+    /// 在旧快照中正常、在新快照中失效的内容
+    pub invalidated: Vec<DiffItem>,
+    pub retitled: Vec<RetitledItem>,
+}
+
+/// 每个内容所在的收藏夹
+fn locate(snapshot: &Snapshot) -> HashMap<ResourceId, (&SnapshotResource, BTreeSet<i64>)> {
+    let mut located: HashMap<ResourceId, (&SnapshotResource, BTreeSet<i64>)> = HashMap::new();
+    for folder in &snapshot.folders {
+        for resource in &folder.resources {
+            located.entry(resource.key())
+                .or_insert_with(|| (resource, BTreeSet::new()))
+                .1.insert(folder.folder.id);
+        }
+    }
+    located
+}
+
+/// 比较两个快照
+///
+/// 只在部分收藏夹中新增或消失的内容记为新增或删除，同时从一些收藏夹消失并出现在另一些收藏夹中的记为移动。
+pub fn diff(old: &Snapshot, new: &Snapshot) -> SnapshotDiff {
+    let before = locate(old);
+    let after = locate(new);
+
+    let mut result = SnapshotDiff {
+        from: old.taken_at,
+        to: new.taken_at,
+        folder_titles: old.folders.iter().chain(&new.folders)
+            .map(|f| (f.folder.id, f.folder.title.clone()))
+            .collect(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        moved: Vec::new(),
+        invalidated: Vec::new(),
+        retitled: Vec::new(),
+    };
+
+    for (key, (resource, old_folders)) in &before {
+        if !after.contains_key(key) {
+            result.removed.push(DiffItem { resource: (*resource).clone(), folder_ids: old_folders.iter().copied().collect() });
+        }
+    }
+
+    for (key, (resource, new_folders)) in &after {
+        let Some((old_resource, old_folders)) = before.get(key) else {
+            result.added.push(DiffItem { resource: (*resource).clone(), folder_ids: new_folders.iter().copied().collect() });
+            continue;
+        };
+
+        let left: Vec<i64> = old_folders.difference(new_folders).copied().collect();
+        let joined: Vec<i64> = new_folders.difference(old_folders).copied().collect();
+        match (left.is_empty(), joined.is_empty()) {
+            (false, false) => result.moved.push(MovedItem { resource: (*resource).clone(), from_folder_ids: left, to_folder_ids: joined }),
+            (false, true) => result.removed.push(DiffItem { resource: (*resource).clone(), folder_ids: left }),
+            (true, false) => result.added.push(DiffItem { resource: (*resource).clone(), folder_ids: joined }),
+            (true, true) => {}
+        }
+
+        let folder_ids: Vec<i64> = new_folders.iter().copied().collect();
+        if resource.is_invalid() && !old_resource.is_invalid() {
+            result.invalidated.push(DiffItem { resource: (*resource).clone(), folder_ids });
+        } else if !resource.is_invalid() && resource.title != old_resource.title {
+            result.retitled.push(RetitledItem { resource: (*resource).clone(), old_title: old_resource.title.clone(), folder_ids });
+        }
+    }
+
+    result.added.sort_by_key(|item| item.resource.id);
+    result.removed.sort_by_key(|item| item.resource.id);
+    result.moved.sort_by_key(|item| item.resource.id);
+    result.invalidated.sort_by_key(|item| item.resource.id);
+    result.retitled.sort_by_key(|item| item.resource.id);
+    result
+}
+
+fn snapshot_dir(up_mid: &str) -> PathBuf {
+    get_config_dir().join("snapshots").join(up_mid)
+}
+
+/// 保存快照，同一秒内已有快照时返回错误而不覆盖
+pub fn save_snapshot(up_mid: &str, snapshot: &Snapshot) -> Result<(), String> {
+    let dir = snapshot_dir(up_mid);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("创建快照目录失败: {}", e))?;
+
+    let content = serde_json::to_string(snapshot)
+        .map_err(|e| format!("序列化快照失败: {}", e))?;
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dir.join(format!("{}.json", snapshot.taken_at)))
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => format!("快照 {} 已存在，请稍后再试", snapshot.taken_at),
+            _ => format!("写入快照失败: {}", e),
+        })?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("写入快照失败: {}", e))
+}
+
+pub fn load_snapshot(up_mid: &str, id: i64) -> Result<Snapshot, String> {
+    let path = snapshot_dir(up_mid).join(format!("{}.json", id));
+    if !path.exists() {
+        return Err(format!("快照 {} 不存在", id));
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("读取快照失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析快照失败: {}", e))
+}
+
+/// 全部快照的ID，从旧到新排列
+pub fn snapshot_ids(up_mid: &str) -> Vec<i64> {
+    let Ok(entries) = fs::read_dir(snapshot_dir(up_mid)) else {
+        return Vec::new();
+    };
+
+    let mut ids: Vec<i64> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_string_lossy().strip_suffix(".json")?.parse().ok())
+        .collect();
+    ids.sort();
+    ids
+}
+
+pub fn delete_snapshot(up_mid: &str, id: i64) -> Result<(), String> {
+    let path = snapshot_dir(up_mid).join(format!("{}.json", id));
+    if path.exists() {
+        fs::remove_file(&path)
+            .map_err(|e| format!("删除快照失败: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(id: i64, title: &str, attr: i32) -> SnapshotResource {
+        SnapshotResource {
+            id,
+            resource_type: ResourceType::Video,
+            bvid: String::new(),
+            title: title.to_string(),
+            attr,
+            upper: None,
+        }
+    }
+
+    fn snapshot(taken_at: i64, folders: Vec<(i64, Vec<SnapshotResource>)>) -> Snapshot {
+        Snapshot {
+            taken_at,
+            folders: folders.into_iter()
+                .map(|(id, resources)| SnapshotFolder {
+                    folder: serde_json::from_value(serde_json::json!({
+                        "id": id, "fid": id, "mid": 1, "title": format!("收藏夹{}", id),
+                        "attr": 0, "fav_state": 0, "media_count": resources.len()
+                    })).unwrap(),
+                    resources,
+                })
+                .collect(),
+        }
+    }
+
+    fn ids(items: &[DiffItem]) -> Vec<(i64, Vec<i64>)> {
+        items.iter().map(|i| (i.resource.id, i.folder_ids.clone())).collect()
+    }
+
+    #[test]
+    fn classifies_changes_between_snapshots() {
+        let old = snapshot(100, vec![
+            (1, vec![resource(1, "不变", 0), resource(2, "将被删除", 0), resource(3, "将被移动", 0), resource(4, "将失效", 0)]),
+            (2, vec![resource(5, "旧标题", 0), resource(6, "两个收藏夹", 0)]),
+        ]);
+        let new = snapshot(200, vec![
+            (1, vec![resource(1, "不变", 0), resource(4, "已失效视频", 9), resource(6, "两个收藏夹", 0)]),
+            (2, vec![resource(3, "将被移动", 0), resource(5, "新标题", 0), resource(6, "两个收藏夹", 0), resource(7, "新增", 0)]),
+        ]);
+
+        let diff = diff(&old, &new);
+        assert_eq!((diff.from, diff.to), (100, 200));
+        // 新出现的内容，以及只在部分收藏夹中新增的内容都记为新增
+        assert_eq!(ids(&diff.added), vec![(6, vec![1]), (7, vec![2])]);
+        assert_eq!(ids(&diff.removed), vec![(2, vec![1])]);
+        assert_eq!(diff.moved.len(), 1);
+        assert_eq!((diff.moved[0].resource.id, diff.moved[0].from_folder_ids.clone(), diff.moved[0].to_folder_ids.clone()), (3, vec![1], vec![2]));
+        assert_eq!(ids(&diff.invalidated), vec![(4, vec![1])]);
+        assert_eq!(diff.retitled.len(), 1);
+        assert_eq!((diff.retitled[0].resource.id, diff.retitled[0].old_title.as_str()), (5, "旧标题"));
+        assert_eq!(diff.folder_titles.len(), 2);
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_changes() {
+        let old = snapshot(100, vec![(1, vec![resource(1, "视频", 0), resource(2, "已失效视频", 1)])]);
+        let new = snapshot(200, vec![(1, vec![resource(1, "视频", 0), resource(2, "已失效视频", 1)])]);
+
+        let diff = diff(&old, &new);
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.moved.is_empty());
+        assert!(diff.invalidated.is_empty() && diff.retitled.is_empty());
+    }
+}
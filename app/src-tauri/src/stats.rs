@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Local};
+use crate::bilibili::{FavoriteFolder, FavoriteResource};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderStats {
+    pub id: i64,
+    pub title: String,
+    pub is_private: bool,
+    pub count: usize,
+    pub invalid_count: usize,
+    pub invalid_ratio: f64,
+    pub total_duration: i64,
+    pub average_duration: f64,
+    pub oldest_fav_time: Option<i64>,
+    pub newest_fav_time: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploaderStats {
+    pub mid: i64,
+    pub name: String,
+    pub count: usize,
+}
+
+/// 直方图中的一个区间，`label` 为月份（YYYY-MM）或年份（YYYY）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub label: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoritesStats {
+    pub folders: Vec<FolderStats>,
+    pub folder_count: usize,
+    pub private_folder_count: usize,
+    pub private_folder_ratio: f64,
+    pub total_count: usize,
+    pub invalid_count: usize,
+    pub total_duration: i64,
+    pub top_uploaders: Vec<UploaderStats>,
+    pub fav_time_by_month: Vec<HistogramBucket>,
+    pub pubtime_by_year: Vec<HistogramBucket>,
+}
+
+fn format_timestamp(timestamp: i64, format: &str) -> Option<String> {
+    DateTime::from_timestamp(timestamp, 0).map(|t| t.with_timezone(&Local).format(format).to_string())
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+fn to_buckets(map: BTreeMap<String, usize>) -> Vec<HistogramBucket> {
+    map.into_iter().map(|(label, count)| HistogramBucket { label, count }).collect()
+}
+
+pub fn folder_stats(folder: &FavoriteFolder, resources: &[FavoriteResource]) -> FolderStats {
+    let count = resources.len();
+    let invalid_count = resources.iter().filter(|r| r.is_invalid()).count();
+    let total_duration: i64 = resources.iter().map(|r| r.duration as i64).sum();
+
+    FolderStats {
+        id: folder.id,
+        title: folder.title.clone(),
+        is_private: folder.is_private(),
+        count,
+        invalid_count,
+        invalid_ratio: ratio(invalid_count, count),
+        total_duration,
+        average_duration: if count == 0 { 0.0 } else { total_duration as f64 / count as f64 },
+        oldest_fav_time: resources.iter().map(|r| r.fav_time).min(),
+        newest_fav_time: resources.iter().map(|r| r.fav_time).max(),
+    }
+}
+
+/// 汇总所有收藏夹的统计数据，`top_n` 为返回的UP主排行数量
+pub fn compute_stats(folders: &[(FavoriteFolder, Vec<FavoriteResource>)], top_n: usize) -> FavoritesStats {
+    let mut uploaders: HashMap<i64, UploaderStats> = HashMap::new();
+    let mut fav_time_by_month = BTreeMap::new();
+    let mut pubtime_by_year = BTreeMap::new();
+
+    for resource in folders.iter().flat_map(|(_, resources)| resources) {
+        if let Some(upper) = &resource.upper {
+            uploaders.entry(upper.mid)
+                .or_insert_with(|| UploaderStats { mid: upper.mid, name: upper.name.clone(), count: 0 })
+                .count += 1;
+        }
+        if let Some(month) = format_timestamp(resource.fav_time, "%Y-%m") {
+            *fav_time_by_month.entry(month).or_insert(0) += 1;
+        }
+        // 失效视频的发布时间通常为 0，不计入分布
+        if resource.pubtime > 0 {
+            if let Some(year) = format_timestamp(resource.pubtime, "%Y") {
+                *pubtime_by_year.entry(year).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut top_uploaders: Vec<UploaderStats> = uploaders.into_values().collect();
+    top_uploaders.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    top_uploaders.truncate(top_n);
+
+    let folder_stats: Vec<FolderStats> = folders.iter().map(|(folder, resources)| folder_stats(folder, resources)).collect();
+    let folder_count = folder_stats.len();
+    let private_folder_count = folder_stats.iter().filter(|f| f.is_private).count();
+
+    FavoritesStats {
+        folder_count,
+        private_folder_count,
+        private_folder_ratio: ratio(private_folder_count, folder_count),
+        total_count: folder_stats.iter().map(|f| f.count).sum(),
+        invalid_count: folder_stats.iter().map(|f| f.invalid_count).sum(),
+        total_duration: folder_stats.iter().map(|f| f.total_duration).sum(),
+        folders: folder_stats,
+        top_uploaders,
+        fav_time_by_month: to_buckets(fav_time_by_month),
+        pubtime_by_year: to_buckets(pubtime_by_year),
+    }
+}
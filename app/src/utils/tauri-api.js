@@ -12,6 +12,55 @@ export const configApi = {
   
   delete: async () => {
     return await invoke('delete_config_command')
+  },
+
+  getHeaderPresets: async () => {
+    return await invoke('get_header_presets')
+  },
+
+  // 校验Cookie是否有效，返回用户名、头像、等级和大会员状态；不传配置时校验已保存的配置
+  verify: async (config = null) => {
+    return await invoke('verify_account', { config })
+  }
+}
+
+// Cookie相关API
+export const cookieApi = {
+  parse: async (input, format = null) => {
+    return await invoke('parse_cookies', { input, format })
+  },
+
+  convert: async (input, to, from = null) => {
+    return await invoke('convert_cookies', { input, from, to })
+  },
+
+  // 解析并保存为配置，支持请求头、JSON数组、cookies.txt和EditThisCookie格式
+  import: async (input, format = null) => {
+    return await invoke('import_cookies', { input, format })
+  },
+
+  getBrowserProfiles: async () => {
+    return await invoke('get_browser_profiles')
+  },
+
+  // 从本机Firefox/Chromium读取登录Cookie，不指定时依次尝试所有配置文件
+  importFromBrowser: async (cookiePath = null) => {
+    return await invoke('import_browser_cookies', { cookiePath })
+  }
+}
+
+// 定时清理相关API
+export const scheduleApi = {
+  get: async () => {
+    return await invoke('get_schedule_config')
+  },
+  
+  save: async (config) => {
+    return await invoke('save_schedule_config', { config })
+  },
+  
+  getLog: async () => {
+    return await invoke('get_schedule_log')
   }
 }
 
@@ -23,6 +72,68 @@ export const favoritesApi = {
   
   getDetails: async (favoriteId) => {
     return await invoke('get_favorite_details', { favoriteId })
+  },
+
+  // 获取收藏夹内的全部内容，优先使用本地缓存
+  getAllResources: async (mediaId, refresh = false) => {
+    return await invoke('get_folder_resources', { mediaId, refresh })
+  },
+
+  // 扫描全部收藏夹并更新本地缓存
+  scan: async () => {
+    return await invoke('scan_favorites')
+  },
+
+  search: async (keyword, limit = null) => {
+    return await invoke('search_favorites', { keyword, limit })
+  },
+
+  // 使用B站的收藏夹内搜索，不指定收藏夹时搜索全部收藏夹
+  searchOnline: async (keyword, mediaId = null) => {
+    return await invoke('search_favorites_online', { keyword, mediaId })
+  },
+
+  clearCache: async () => {
+    return await invoke('clear_favorites_cache')
+  }
+}
+
+// 收藏和订阅相关API
+export const collectedApi = {
+  get: async () => {
+    return await invoke('get_collected_folders')
+  },
+  
+  unsubscribe: async (ids, onlyUnavailable = false) => {
+    return await invoke('unsubscribe_collected', { ids, all: false, onlyUnavailable })
+  },
+
+  // 处理全部收藏/订阅，需要显式调用
+  unsubscribeAll: async (onlyUnavailable = false) => {
+    return await invoke('unsubscribe_collected', { ids: [], all: true, onlyUnavailable })
+  }
+}
+
+// 稍后再看相关API
+export const watchLaterApi = {
+  get: async () => {
+    return await invoke('get_watch_later')
+  },
+  
+  add: async (bvid) => {
+    return await invoke('add_to_watch_later', { bvid })
+  },
+  
+  delete: async (aids) => {
+    return await invoke('delete_from_watch_later', { aids })
+  },
+  
+  moveToFolder: async (aids, targetId) => {
+    return await invoke('move_watch_later_to_folder', { aids, targetId })
+  },
+  
+  clean: async ({ watched = true, invalid = true } = {}) => {
+    return await invoke('clean_watch_later', { watched, invalid })
   }
 }
 
@@ -37,6 +148,52 @@ export const cleanApi = {
   }
 }
 
+// 可用性检查相关API
+export const availabilityApi = {
+  // 不传收藏夹ID时检查全部收藏夹
+  check: async (mediaIds = []) => {
+    return await invoke('check_availability', { mediaIds })
+  },
+
+  getReport: async () => {
+    return await invoke('get_availability_report')
+  },
+
+  // 后台定期检查发现新失效视频时会发出 invalidated-videos-detected 事件
+  detectInvalidated: async () => {
+    return await invoke('detect_invalidated_videos')
+  },
+
+  getInvalidatedHistory: async () => {
+    return await invoke('get_invalidated_history')
+  },
+
+  // categories: deleted_by_uploader / copyright / private / region_restricted / paid_only
+  clean: async (categories) => {
+    return await invoke('clean_by_availability', { categories })
+  }
+}
+
+// 快照相关API
+export const snapshotApi = {
+  take: async () => {
+    return await invoke('take_snapshot')
+  },
+
+  list: async () => {
+    return await invoke('list_snapshots')
+  },
+
+  // from 为较早的快照ID
+  diff: async (from, to) => {
+    return await invoke('diff_snapshots', { from, to })
+  },
+
+  delete: async (id) => {
+    return await invoke('delete_snapshot', { id })
+  }
+}
+
 // 移动相关API
 export const moveApi = {
   getFavorites: async () => {
@@ -47,8 +204,21 @@ export const moveApi = {
     return await invoke('get_move_videos', { sourceId, targetId })
   },
   
+  planMove: async (sourceId, targetId, filter = {}, direction = 'newest_first') => {
+    return await invoke('plan_move', { sourceId, targetId, filter, direction })
+  },
+  
   moveVideos: async (sourceId, targetId, videoIds) => {
     return await invoke('move_videos', { sourceId, targetId, videoIds })
+  },
+  
+  // resources: [{ id, type }]
+  copyResources: async (sourceId, targetId, resources) => {
+    return await invoke('copy_resources', { sourceId, targetId, resources })
+  },
+  
+  deleteResources: async (mediaId, resources) => {
+    return await invoke('delete_resources', { mediaId, resources })
   }
 }
 
@@ -60,5 +230,57 @@ export const sortApi = {
   
   executeSort: async (folderIds) => {
     return await invoke('execute_sort', { folderIds })
+  },
+
+  sortResources: async (mediaId, { resourceIds = null, sortKey = null, descending = false } = {}) => {
+    return await invoke('sort_folder_resources', { mediaId, resourceIds, sortKey, descending })
+  }
+}
+
+// 收藏夹整理相关API
+export const organizeApi = {
+  merge: async (sourceIds, targetId, deleteEmptied = false) => {
+    return await invoke('merge_folders', { sourceIds, targetId, deleteEmptied })
+  },
+
+  deleteFolders: async (mediaIds) => {
+    return await invoke('delete_favorite_folders', { mediaIds })
+  },
+
+  // criterion: { type: 'year' | 'quarter' | 'uploader' | 'partition' } 或 { type: 'duration', bounds: [5, 20, 60] }
+  // nameTemplate 可使用 {title}、{group}、{count}、{index}
+  previewSplit: async (mediaId, criterion, nameTemplate = '{title}-{group}', minCount = null) => {
+    return await invoke('preview_split_folder', { mediaId, criterion, nameTemplate, minCount })
+  },
+
+  split: async (mediaId, criterion, nameTemplate = '{title}-{group}', minCount = null) => {
+    return await invoke('split_folder', { mediaId, criterion, nameTemplate, minCount })
+  },
+
+  // rule: { type: 'template', template: '{index:02} {title}' } 或 { type: 'regex', pattern, replacement }
+  previewRename: async (mediaIds, rule) => {
+    return await invoke('preview_rename_folders', { mediaIds, rule })
+  },
+
+  rename: async (mediaIds, rule) => {
+    return await invoke('rename_folders', { mediaIds, rule })
   }
-}
\ No newline at end of file
+}
+
+// 公开状态相关API
+export const privacyApi = {
+  audit: async (sampleSize = null) => {
+    return await invoke('audit_folder_privacy', { sampleSize })
+  },
+
+  setPrivacy: async (mediaIds, isPrivate) => {
+    return await invoke('set_folders_privacy', { mediaIds, private: isPrivate })
+  }
+}
+
+// 统计相关API
+export const statsApi = {
+  get: async (topN = 20) => {
+    return await invoke('get_favorites_stats', { topN })
+  }
+}
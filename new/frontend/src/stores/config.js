@@ -9,8 +9,13 @@ export const useConfigStore = defineStore('config', () => {
   const error = ref(null)
 
   // 计算属性
+  // 接口不返回 Cookie 和 csrf_token，只返回 hasCookie；旧版后端仍返回 cookie 字段
+  const hasCookie = computed(() => {
+    return Boolean(config.value && (config.value.hasCookie ?? config.value.cookie))
+  })
+
   const isConfigured = computed(() => {
-    return config.value && config.value.up_mid && config.value.csrf_token && config.value.cookie
+    return Boolean(config.value && config.value.up_mid && hasCookie.value)
   })
 
   const statusText = computed(() => {
@@ -50,7 +55,8 @@ export const useConfigStore = defineStore('config', () => {
     
     try {
       const response = await api.post('/config', newConfig)
-      config.value = newConfig
+      const { cookie, csrf_token, ...rest } = newConfig
+      config.value = { ...rest, hasCookie: Boolean(cookie && csrf_token) }
       return response
     } catch (err) {
       console.error('保存配置失败:', err)
@@ -88,6 +94,7 @@ export const useConfigStore = defineStore('config', () => {
     error,
     
     // 计算属性
+    hasCookie,
     isConfigured,
     statusText,
     statusClass,